@@ -0,0 +1,276 @@
+use std::collections::BTreeMap;
+
+use crate::filesystem::{DirId, Filesystem, ROOT};
+use crate::report::format_size;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Kind {
+    File,
+    Dir,
+}
+
+impl Kind {
+    fn name(self) -> &'static str {
+        match self {
+            Kind::File => "file",
+            Kind::Dir => "dir",
+        }
+    }
+}
+
+/// A difference between two file systems. Added and removed directories are reported as a
+/// whole, without their contents.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Change {
+    Added {
+        path: String,
+        kind: Kind,
+        size: usize,
+    },
+    Removed {
+        path: String,
+        kind: Kind,
+        size: usize,
+    },
+    Resized {
+        path: String,
+        kind: Kind,
+        before: usize,
+        after: usize,
+    },
+}
+
+impl Change {
+    pub fn path(&self) -> &str {
+        match self {
+            Change::Added { path, .. }
+            | Change::Removed { path, .. }
+            | Change::Resized { path, .. } => path,
+        }
+    }
+
+    /// How much the size grew, negative if it shrank.
+    pub fn delta(&self) -> isize {
+        match self {
+            Change::Added { size, .. } => *size as isize,
+            Change::Removed { size, .. } => -(*size as isize),
+            Change::Resized { before, after, .. } => *after as isize - *before as isize,
+        }
+    }
+
+    /// Formats the change like `~ /a (dir, size=100 -> 150, +50)`, with sizes like `2.5K` if
+    /// `human` is set.
+    pub fn format(&self, human: bool) -> String {
+        let size = |size| format_size(size, human);
+        match self {
+            Change::Added {
+                path,
+                kind,
+                size: added,
+            } => {
+                format!("+ {path} ({}, size={})", kind.name(), size(*added))
+            }
+            Change::Removed {
+                path,
+                kind,
+                size: removed,
+            } => {
+                format!("- {path} ({}, size={})", kind.name(), size(*removed))
+            }
+            Change::Resized {
+                path,
+                kind,
+                before,
+                after,
+            } => {
+                let sign = if after > before { '+' } else { '-' };
+                format!(
+                    "~ {path} ({}, size={} -> {}, {sign}{})",
+                    kind.name(),
+                    size(*before),
+                    size(*after),
+                    size(before.abs_diff(*after))
+                )
+            }
+        }
+    }
+}
+
+/// An entry of a directory in both file systems.
+#[derive(Default)]
+struct Pair {
+    files: [Option<usize>; 2],
+    dirs: [Option<DirId>; 2],
+}
+
+enum Step {
+    Compare(DirId, DirId),
+    Report(Change),
+    /// Goes back up to the directory whose path is this long.
+    Leave(usize),
+}
+
+/// Compares two file systems, listing the changes in the order of a depth-first walk with the
+/// entries of each directory sorted by name. Every directory that is in both is compared
+/// entry by entry, and reported as resized if its total size changed.
+pub fn diff(before: &Filesystem, after: &Filesystem) -> Vec<Change> {
+    let mut changes = Vec::new();
+    // an explicit stack, as transcripts can be nested arbitrarily deep
+    let mut stack = vec![Step::Compare(ROOT, ROOT)];
+    // the path of the directory being compared, without the `/` of the root
+    let mut path = String::new();
+    while let Some(step) = stack.pop() {
+        let (old, new) = match step {
+            Step::Compare(old, new) => (old, new),
+            Step::Report(change) => {
+                changes.push(change);
+                continue;
+            }
+            Step::Leave(len) => {
+                path.truncate(len);
+                continue;
+            }
+        };
+        let parent_len = path.len();
+        if old != ROOT {
+            path.push('/');
+            path.push_str(before.name(old));
+        }
+        if before.size(old) != after.size(new) {
+            changes.push(Change::Resized {
+                path: if old == ROOT {
+                    "/".to_string()
+                } else {
+                    path.clone()
+                },
+                kind: Kind::Dir,
+                before: before.size(old),
+                after: after.size(new),
+            });
+        }
+
+        let mut entries: BTreeMap<&str, Pair> = BTreeMap::new();
+        for (side, (filesystem, dir)) in [(before, old), (after, new)].into_iter().enumerate() {
+            for (name, size) in filesystem.files(dir) {
+                entries.entry(name).or_default().files[side] = Some(size);
+            }
+            for subdir in filesystem.subdirs(dir) {
+                entries.entry(filesystem.name(subdir)).or_default().dirs[side] = Some(subdir);
+            }
+        }
+
+        let mut steps = Vec::new();
+        for (name, pair) in entries {
+            let path = format!("{path}/{name}");
+            match pair.files {
+                [Some(old), Some(new)] if old != new => steps.push(Step::Report(Change::Resized {
+                    path: path.clone(),
+                    kind: Kind::File,
+                    before: old,
+                    after: new,
+                })),
+                [Some(size), None] => steps.push(Step::Report(Change::Removed {
+                    path: path.clone(),
+                    kind: Kind::File,
+                    size,
+                })),
+                [None, Some(size)] => steps.push(Step::Report(Change::Added {
+                    path: path.clone(),
+                    kind: Kind::File,
+                    size,
+                })),
+                _ => {}
+            }
+            match pair.dirs {
+                [Some(old), Some(new)] => steps.push(Step::Compare(old, new)),
+                [Some(old), None] => steps.push(Step::Report(Change::Removed {
+                    path,
+                    kind: Kind::Dir,
+                    size: before.size(old),
+                })),
+                [None, Some(new)] => steps.push(Step::Report(Change::Added {
+                    path,
+                    kind: Kind::Dir,
+                    size: after.size(new),
+                })),
+                [None, None] => {}
+            }
+        }
+        stack.push(Step::Leave(parent_len));
+        stack.extend(steps.into_iter().rev());
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{load, EX};
+
+    #[test]
+    fn same_filesystem() {
+        assert_eq!(diff(&load(EX), &load(EX)), []);
+    }
+
+    #[test]
+    fn changes() {
+        let before = load(
+            "$ ls\ndir a\ndir b\n10 x\n\
+                           $ cd a\n$ ls\n5 y\n7 z\n$ cd ..\n$ cd b\n$ ls\n3 w\n",
+        );
+        let after = load(
+            "$ ls\ndir a\ndir c\n10 x\n\
+                          $ cd a\n$ ls\n8 y\n$ cd ..\n$ cd c\n$ ls\n4 v\n",
+        );
+        let changes: Vec<String> = diff(&before, &after)
+            .iter()
+            .map(|change| change.format(false))
+            .collect();
+        assert_eq!(
+            changes,
+            [
+                "~ / (dir, size=25 -> 22, -3)",
+                "~ /a (dir, size=12 -> 8, -4)",
+                "~ /a/y (file, size=5 -> 8, +3)",
+                "- /a/z (file, size=7)",
+                "- /b (dir, size=3)",
+                "+ /c (dir, size=4)",
+            ]
+        );
+        let deltas: Vec<isize> = diff(&before, &after).iter().map(Change::delta).collect();
+        assert_eq!(deltas, [-3, -4, 3, -7, -3, 4]);
+    }
+
+    #[test]
+    fn file_replaced_by_dir() {
+        let before = load("$ ls\n10 x\n");
+        let after = load("$ ls\ndir x\n$ cd x\n$ ls\n10 y\n");
+        assert_eq!(
+            diff(&before, &after),
+            [
+                Change::Removed {
+                    path: "/x".to_string(),
+                    kind: Kind::File,
+                    size: 10
+                },
+                Change::Added {
+                    path: "/x".to_string(),
+                    kind: Kind::Dir,
+                    size: 10
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn cleanup() {
+        let before = load(EX);
+        let after = load(&EX.replace("$ cd d\n$ ls\n4060174 j\n8033020 d.log\n", "$ cd d\n$ ls\n"));
+        let changes = diff(&before, &after);
+        assert_eq!(changes.len(), 4);
+        assert_eq!(changes[0].path(), "/");
+        assert_eq!(changes[0].delta(), -12093194);
+        assert_eq!(changes[1].format(true), "~ /d (dir, size=24M -> 12M, -12M)");
+        assert_eq!(changes[2].format(false), "- /d/d.log (file, size=8033020)");
+    }
+}
@@ -0,0 +1,385 @@
+use aoc_common::ParseError;
+
+use crate::filesystem::{Filesystem, ROOT};
+use crate::parser::{Command, Entry, Target};
+
+/// Whether `name` can be written to a transcript as the name of a file or directory, so that
+/// it reads back as the same entry. Spaces are fine, except at the end where the parser
+/// trims them off.
+fn valid_name(name: &str) -> bool {
+    !name.is_empty() && name != ".." && name.trim_end() == name && !name.contains(['/', '\n', '\r'])
+}
+
+fn json_string(text: &str) -> String {
+    serde_json::to_string(text).expect("strings can always be serialized")
+}
+
+/// Exports the tree as nested JSON objects, like
+/// `{"size":3,"files":{"b":1},"dirs":{"a":{"size":2,"files":{"c":2},"dirs":{}}}}`. The size
+/// is only there for other tools and is recomputed on import.
+pub fn to_json(filesystem: &Filesystem) -> String {
+    let mut json = String::new();
+    // an explicit stack, as trees can be nested arbitrarily deep; `None` closes a directory
+    let mut stack = vec![Some((ROOT, true))];
+    while let Some(next) = stack.pop() {
+        let Some((dir, first)) = next else {
+            json.push_str("}}");
+            continue;
+        };
+        if !first {
+            json.push(',');
+        }
+        if dir != ROOT {
+            json.push_str(&json_string(filesystem.name(dir)));
+            json.push(':');
+        }
+        json.push_str(&format!("{{\"size\":{},\"files\":{{", filesystem.size(dir)));
+        for (idx, (name, size)) in filesystem.files(dir).into_iter().enumerate() {
+            if idx > 0 {
+                json.push(',');
+            }
+            json.push_str(&format!("{}:{size}", json_string(name)));
+        }
+        json.push_str("},\"dirs\":{");
+        stack.push(None);
+        let subdirs = filesystem.subdirs(dir);
+        for (idx, &subdir) in subdirs.iter().enumerate().rev() {
+            stack.push(Some((subdir, idx == 0)));
+        }
+    }
+    json
+}
+
+/// Reads the JSON written by [`to_json`], one token at a time.
+struct JsonReader<'a> {
+    json: &'a str,
+    pos: usize,
+}
+
+impl JsonReader<'_> {
+    fn error(&self, message: &str) -> ParseError {
+        ParseError::at_offset(self.json, self.pos, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.json[self.pos..];
+        self.pos += rest.len() - rest.trim_start_matches([' ', '\t', '\n', '\r']).len();
+    }
+
+    /// Skips `token` if it comes next.
+    fn eat(&mut self, token: u8) -> bool {
+        self.skip_whitespace();
+        let found = self.json.as_bytes().get(self.pos) == Some(&token);
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    fn expect(&mut self, token: u8) -> Result<(), ParseError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", token as char)))
+        }
+    }
+
+    /// Expects the `,` before every member of an object but the first.
+    fn separator(&mut self, first: &mut bool) -> Result<(), ParseError> {
+        if !std::mem::take(first) {
+            self.expect(b',')?;
+        }
+        Ok(())
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        self.expect(b'"')?;
+        let bytes = self.json.as_bytes();
+        loop {
+            match bytes.get(self.pos) {
+                None => return Err(self.error("unterminated string")),
+                Some(b'"') => break,
+                Some(b'\\') => self.pos += 2,
+                Some(_) => self.pos += 1,
+            }
+        }
+        self.pos += 1;
+        serde_json::from_str(&self.json[start..self.pos])
+            .map_err(|_| ParseError::at_offset(self.json, start, "invalid string"))
+    }
+
+    /// A name of a file or directory, which has to fit into a transcript.
+    fn name(&mut self) -> Result<String, ParseError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        let name = self.string()?;
+        if !valid_name(&name) {
+            let message = "cannot be the name of a file or directory";
+            let message = format!("{} {message}", json_string(&name));
+            return Err(ParseError::at_offset(self.json, start, message));
+        }
+        self.expect(b':')?;
+        Ok(name)
+    }
+
+    fn size(&mut self) -> Result<usize, ParseError> {
+        self.skip_whitespace();
+        let rest = &self.json[self.pos..];
+        let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        let size = rest[..digits]
+            .parse()
+            .map_err(|_| self.error("expected a size"))?;
+        self.pos += digits;
+        Ok(size)
+    }
+}
+
+/// What is read next inside of a directory.
+struct OpenDir {
+    entries: Vec<Entry>,
+    first_member: bool,
+    /// Whether the reader is inside of the `dirs` object, and if so, whether no subdirectory
+    /// was read yet.
+    first_subdir: Option<bool>,
+}
+
+impl OpenDir {
+    fn new() -> OpenDir {
+        OpenDir {
+            entries: Vec::new(),
+            first_member: true,
+            first_subdir: None,
+        }
+    }
+}
+
+/// Imports a tree exported by [`to_json`]. The sizes of the directories may be left out.
+///
+/// The JSON is turned into the terminal session that explores the tree as it is read, with a
+/// stack of the directories that are still open instead of recursion, so that there is no
+/// limit on how deep the tree can be.
+pub fn from_json(json: &str) -> Result<Filesystem, ParseError> {
+    let mut reader = JsonReader { json, pos: 0 };
+    let mut commands = vec![Command::Cd(Target::Root)];
+    reader.expect(b'{')?;
+    let mut stack = vec![OpenDir::new()];
+    while let Some(dir) = stack.last_mut() {
+        if let Some(first_subdir) = &mut dir.first_subdir {
+            if reader.eat(b'}') {
+                dir.first_subdir = None;
+                continue;
+            }
+            reader.separator(first_subdir)?;
+            let name = reader.name()?;
+            reader.expect(b'{')?;
+            dir.entries.push(Entry::Dir { name: name.clone() });
+            commands.push(Command::Cd(Target::Dir(name)));
+            stack.push(OpenDir::new());
+            continue;
+        }
+
+        if reader.eat(b'}') {
+            let dir = stack.pop().expect("the directory is open");
+            commands.push(Command::Ls(dir.entries));
+            if !stack.is_empty() {
+                commands.push(Command::Cd(Target::Parent));
+            }
+            continue;
+        }
+        reader.separator(&mut dir.first_member)?;
+        let key_pos = reader.pos;
+        match reader.string()?.as_str() {
+            "size" => {
+                reader.expect(b':')?;
+                reader.size()?;
+            }
+            "files" => {
+                reader.expect(b':')?;
+                reader.expect(b'{')?;
+                let mut first = true;
+                while !reader.eat(b'}') {
+                    reader.separator(&mut first)?;
+                    let name = reader.name()?;
+                    let size = reader.size()?;
+                    dir.entries.push(Entry::File { name, size });
+                }
+            }
+            "dirs" => {
+                reader.expect(b':')?;
+                reader.expect(b'{')?;
+                dir.first_subdir = Some(true);
+            }
+            key => {
+                let message = format!("unexpected key {}", json_string(key));
+                return Err(ParseError::at_offset(json, key_pos, message));
+            }
+        }
+    }
+    reader.skip_whitespace();
+    if reader.pos < json.len() {
+        return Err(reader.error("unexpected input after the tree"));
+    }
+    Ok(Filesystem::from_commands(&commands))
+}
+
+/// Exports the tree as the terminal session that explores it depth-first, listing every
+/// directory once with its entries sorted by name.
+pub fn to_transcript(filesystem: &Filesystem) -> String {
+    let mut transcript = "$ cd /\n".to_string();
+    // an explicit stack, as transcripts can be nested arbitrarily deep
+    let mut stack = vec![Some(ROOT)];
+    while let Some(next) = stack.pop() {
+        let Some(dir) = next else {
+            transcript.push_str("$ cd ..\n");
+            continue;
+        };
+        if dir != ROOT {
+            transcript.push_str(&format!("$ cd {}\n", filesystem.name(dir)));
+        }
+        transcript.push_str("$ ls\n");
+        let subdirs = filesystem.subdirs(dir);
+        let mut entries: Vec<(&str, String)> = subdirs
+            .iter()
+            .map(|&subdir| {
+                (
+                    filesystem.name(subdir),
+                    format!("dir {}", filesystem.name(subdir)),
+                )
+            })
+            .chain(
+                filesystem
+                    .files(dir)
+                    .into_iter()
+                    .map(|(name, size)| (name, format!("{size} {name}"))),
+            )
+            .collect();
+        entries.sort();
+        for (_, entry) in entries {
+            transcript.push_str(&entry);
+            transcript.push('\n');
+        }
+        for &subdir in subdirs.iter().rev() {
+            stack.push(None);
+            stack.push(Some(subdir));
+        }
+    }
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{load, EX};
+    use crate::generate::generate;
+    use crate::report::du;
+
+    #[test]
+    fn example_transcript() {
+        let expected = "\
+$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+5626152 d.ext
+8033020 d.log
+4060174 j
+7214296 k
+$ cd ..
+";
+        assert_eq!(to_transcript(&load(EX)), expected);
+    }
+
+    #[test]
+    fn example_json() {
+        let json = to_json(&load(EX));
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["size"], 48381165);
+        assert_eq!(value["files"]["b.txt"], 14848514);
+        assert_eq!(value["dirs"]["a"]["dirs"]["e"]["files"]["i"], 584);
+    }
+
+    #[test]
+    fn json_without_sizes() {
+        let json = r#"{"files": {"b": 1}, "dirs": {"a": {"files": {"c": 2}}}}"#;
+        let filesystem = from_json(json).unwrap();
+        assert_eq!(du(&filesystem, false), "3\t/\n2\t/a\n");
+        let err = from_json(r#"{"files": {"b": "large"}}"#).unwrap_err();
+        assert_eq!(err, ParseError::new(1, 17, "expected a size"));
+        let err = from_json("{\n  \"dirs\": {}\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 1, "expected ','"));
+        assert!(from_json(r#"{"size": 1} {}"#).is_err());
+        assert!(from_json(r#"{"links": {}}"#).is_err());
+    }
+
+    #[test]
+    fn names_that_cannot_be_listed() {
+        for name in ["", "..", "/", "a/b", "a\\nb", "a "] {
+            let json = format!(r#"{{"files": {{"{name}": 1}}}}"#);
+            assert!(from_json(&json).is_err(), "{json}");
+            let json = format!(r#"{{"dirs": {{"ok": {{"dirs": {{"{name}": {{}}}}}}}}}}"#);
+            let err = from_json(&json).unwrap_err();
+            assert_eq!(err.column, 27, "{json}");
+            assert!(err
+                .message
+                .ends_with("cannot be the name of a file or directory"));
+        }
+        assert!(from_json(r#"{"dirs": {".": {}, "a.b": {"files": {"-": 1}}}}"#).is_ok());
+    }
+
+    #[test]
+    fn deep_round_trip() {
+        let depth = 10_000;
+        let mut transcript = "$ cd /\n".to_string();
+        for _ in 0..depth {
+            transcript.push_str("$ ls\ndir d\n1 f\n$ cd d\n");
+        }
+        transcript.push_str("$ ls\n2 g\n");
+        let filesystem = load(&transcript);
+
+        let json = to_json(&filesystem);
+        let imported = from_json(&json).unwrap();
+        assert_eq!(imported.size(ROOT), depth + 2);
+        assert_eq!(to_json(&imported), json);
+        assert_eq!(to_transcript(&imported), to_transcript(&filesystem));
+    }
+
+    #[test]
+    fn round_trips() {
+        let transcripts = [
+            EX.to_string(),
+            "$ ls\ndir a b\n1 c d\n$ cd a b\n$ ls\n2  e\n".to_string(),
+            generate(1, 10),
+            generate(2, 30),
+            generate(3, 0),
+        ];
+        for transcript in transcripts {
+            let filesystem = load(&transcript);
+
+            let canonical = to_transcript(&filesystem);
+            assert_eq!(du(&load(&canonical), false), du(&filesystem, false));
+            assert_eq!(to_transcript(&load(&canonical)), canonical);
+
+            let json = to_json(&filesystem);
+            let imported = from_json(&json).unwrap();
+            assert_eq!(to_json(&imported), json);
+            assert_eq!(to_transcript(&imported), canonical);
+        }
+    }
+}
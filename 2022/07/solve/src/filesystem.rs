@@ -0,0 +1,315 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use crate::parser::{Command, Entry, Target};
+
+/// Index of a directory in the arena of a [`Filesystem`].
+pub type DirId = usize;
+
+/// The root directory is always the first one.
+pub const ROOT: DirId = 0;
+
+#[derive(Debug)]
+struct Dir {
+    name: String,
+    parent: Option<DirId>,
+    dirs: HashMap<String, DirId>,
+    files: HashMap<String, usize>,
+    /// Total size of the files in this directory and below.
+    size: usize,
+}
+
+impl Dir {
+    fn new(name: String, parent: Option<DirId>) -> Dir {
+        Dir {
+            name,
+            parent,
+            dirs: HashMap::new(),
+            files: HashMap::new(),
+            size: 0,
+        }
+    }
+}
+
+/// A directory tree stored in an arena, in which every directory knows its parent.
+///
+/// Directories are only ever added after their parent, so a directory's id is larger than its
+/// parent's.
+#[derive(Debug)]
+pub struct Filesystem {
+    dirs: Vec<Dir>,
+}
+
+impl Filesystem {
+    fn new() -> Filesystem {
+        Filesystem {
+            dirs: vec![Dir::new(String::new(), None)],
+        }
+    }
+
+    /// Replays a terminal session. Like a shell, `cd ..` stays in the root directory.
+    pub fn from_commands(commands: &[Command]) -> Filesystem {
+        let mut filesystem = Filesystem::new();
+        let mut cwd = ROOT;
+        for command in commands {
+            match command {
+                Command::Cd(Target::Root) => cwd = ROOT,
+                Command::Cd(Target::Parent) => cwd = filesystem.parent(cwd).unwrap_or(ROOT),
+                Command::Cd(Target::Dir(name)) => cwd = filesystem.add_dir(cwd, name),
+                Command::Ls(entries) => {
+                    for entry in entries {
+                        match entry {
+                            Entry::File { name, size } => filesystem.add_file(cwd, name, *size),
+                            Entry::Dir { name } => {
+                                filesystem.add_dir(cwd, name);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        filesystem.update_sizes();
+        filesystem
+    }
+
+    /// Returns the subdirectory `name` of `parent`, creating it if necessary.
+    fn add_dir(&mut self, parent: DirId, name: &str) -> DirId {
+        if let Some(&id) = self.dirs[parent].dirs.get(name) {
+            return id;
+        }
+        let id = self.dirs.len();
+        self.dirs.push(Dir::new(name.to_string(), Some(parent)));
+        self.dirs[parent].dirs.insert(name.to_string(), id);
+        id
+    }
+
+    fn add_file(&mut self, dir: DirId, name: &str, size: usize) {
+        self.dirs[dir].files.insert(name.to_string(), size);
+    }
+
+    /// Caches the total size of every directory, children first.
+    fn update_sizes(&mut self) {
+        for dir in &mut self.dirs {
+            dir.size = dir.files.values().sum();
+        }
+        for id in (1..self.dirs.len()).rev() {
+            let size = self.dirs[id].size;
+            if let Some(parent) = self.dirs[id].parent {
+                self.dirs[parent].size += size;
+            }
+        }
+    }
+
+    pub fn name(&self, dir: DirId) -> &str {
+        &self.dirs[dir].name
+    }
+
+    pub fn parent(&self, dir: DirId) -> Option<DirId> {
+        self.dirs[dir].parent
+    }
+
+    pub fn subdir(&self, dir: DirId, name: &str) -> Option<DirId> {
+        self.dirs[dir].dirs.get(name).copied()
+    }
+
+    /// The total size of the files in `dir` and below.
+    pub fn size(&self, dir: DirId) -> usize {
+        self.dirs[dir].size
+    }
+
+    /// The ids of all directories, including the root.
+    pub fn dirs(&self) -> impl Iterator<Item = DirId> {
+        0..self.dirs.len()
+    }
+
+    /// The subdirectories of `dir`, sorted by name.
+    pub fn subdirs(&self, dir: DirId) -> Vec<DirId> {
+        let mut subdirs: Vec<DirId> = self.dirs[dir].dirs.values().copied().collect();
+        subdirs.sort_by_key(|&id| self.name(id));
+        subdirs
+    }
+
+    /// The names and sizes of the files in `dir`, sorted by name.
+    pub fn files(&self, dir: DirId) -> Vec<(&str, usize)> {
+        let mut files: Vec<(&str, usize)> = self.dirs[dir]
+            .files
+            .iter()
+            .map(|(name, size)| (name.as_str(), *size))
+            .collect();
+        files.sort();
+        files
+    }
+
+    /// The absolute path of `dir`, like `/a/e`.
+    pub fn path(&self, dir: DirId) -> String {
+        let mut names = Vec::new();
+        let mut current = dir;
+        while let Some(parent) = self.parent(current) {
+            names.push(self.name(current));
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Calls `visit` with every directory and its path, depth-first with the subdirectories
+    /// sorted by name. The path is built up one name at a time on the way down, instead of
+    /// from the root for every directory.
+    pub fn walk_paths(&self, mut visit: impl FnMut(DirId, &str)) {
+        let mut path = String::new();
+        // directories still to visit, with the length of their parent's path
+        let mut stack = vec![(ROOT, 0)];
+        while let Some((dir, parent_len)) = stack.pop() {
+            path.truncate(parent_len);
+            if dir != ROOT {
+                path.push('/');
+                path.push_str(self.name(dir));
+            }
+            visit(dir, if dir == ROOT { "/" } else { &path });
+            let subdirs = self.subdirs(dir).into_iter().rev();
+            stack.extend(subdirs.map(|subdir| (subdir, path.len())));
+        }
+    }
+
+    /// Looks up the directory at the absolute `path`.
+    pub fn find_dir(&self, path: &str) -> Option<DirId> {
+        let names = path.strip_prefix('/')?;
+        names
+            .split('/')
+            .filter(|name| !name.is_empty())
+            .try_fold(ROOT, |dir, name| self.subdir(dir, name))
+    }
+
+    /// The size of the file or directory at the absolute `path`.
+    pub fn size_of(&self, path: &str) -> Option<usize> {
+        if let Some(dir) = self.find_dir(path) {
+            return Some(self.size(dir));
+        }
+        let (parent, name) = path.trim_end_matches('/').rsplit_once('/')?;
+        let parent = self.find_dir(if parent.is_empty() { "/" } else { parent })?;
+        self.dirs[parent].files.get(name).copied()
+    }
+
+    /// The `count` largest directories, largest first; equally large ones are in the order of
+    /// [`Filesystem::walk_paths`].
+    pub fn largest(&self, count: usize) -> Vec<DirId> {
+        let mut by_path = vec![0; self.dirs.len()];
+        let mut next = 0;
+        self.walk_paths(|dir, _| {
+            by_path[dir] = next;
+            next += 1;
+        });
+        let mut dirs: Vec<DirId> = self.dirs().collect();
+        dirs.sort_by_key(|&dir| (Reverse(self.size(dir)), by_path[dir]));
+        dirs.truncate(count);
+        dirs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::{load, EX};
+
+    #[test]
+    fn cached_sizes() {
+        let filesystem = load(EX);
+        let a = filesystem.subdir(ROOT, "a").unwrap();
+        let e = filesystem.subdir(a, "e").unwrap();
+        let d = filesystem.subdir(ROOT, "d").unwrap();
+        assert_eq!(filesystem.size(e), 584);
+        assert_eq!(filesystem.size(a), 94853);
+        assert_eq!(filesystem.size(d), 24933642);
+        assert_eq!(filesystem.size(ROOT), 48381165);
+        assert_eq!(filesystem.parent(e), Some(a));
+        assert_eq!(filesystem.name(e), "e");
+    }
+
+    #[test]
+    fn listed_dirs_are_created() {
+        let filesystem = load("$ cd /\n$ ls\ndir a\n");
+        let a = filesystem.subdir(ROOT, "a").unwrap();
+        assert_eq!(filesystem.size(a), 0);
+        assert_eq!(filesystem.dirs().count(), 2);
+    }
+
+    #[test]
+    fn listing_again_replaces_files() {
+        let filesystem = load("$ ls\n10 a\n20 b\n$ ls\n15 a\n$ cd ..\n$ ls\n1 c\n");
+        assert_eq!(filesystem.size(ROOT), 36);
+    }
+
+    #[test]
+    fn paths() {
+        let filesystem = load(EX);
+        let e = filesystem.find_dir("/a/e").unwrap();
+        assert_eq!(filesystem.path(e), "/a/e");
+        assert_eq!(filesystem.path(ROOT), "/");
+        assert_eq!(filesystem.find_dir("/"), Some(ROOT));
+        assert_eq!(filesystem.find_dir("/a/e/"), Some(e));
+        assert_eq!(filesystem.find_dir("/a/x"), None);
+        assert_eq!(filesystem.find_dir("a/e"), None);
+    }
+
+    #[test]
+    fn size_of_paths() {
+        let filesystem = load(EX);
+        assert_eq!(filesystem.size_of("/"), Some(48381165));
+        assert_eq!(filesystem.size_of("/a"), Some(94853));
+        assert_eq!(filesystem.size_of("/a/e/i"), Some(584));
+        assert_eq!(filesystem.size_of("/b.txt"), Some(14848514));
+        assert_eq!(filesystem.size_of("/a/b.txt"), None);
+        assert_eq!(filesystem.size_of("/x/i"), None);
+    }
+
+    #[test]
+    fn listings() {
+        let filesystem = load(EX);
+        let names: Vec<&str> = filesystem
+            .subdirs(ROOT)
+            .into_iter()
+            .map(|dir| filesystem.name(dir))
+            .collect();
+        assert_eq!(names, ["a", "d"]);
+        let a = filesystem.find_dir("/a").unwrap();
+        assert_eq!(
+            filesystem.files(a),
+            [("f", 29116), ("g", 2557), ("h.lst", 62596)]
+        );
+    }
+
+    #[test]
+    fn largest_dirs() {
+        let filesystem = load(EX);
+        let paths: Vec<String> = filesystem
+            .largest(3)
+            .into_iter()
+            .map(|dir| filesystem.path(dir))
+            .collect();
+        assert_eq!(paths, ["/", "/d", "/a"]);
+        assert_eq!(filesystem.largest(10).len(), 4);
+    }
+
+    #[test]
+    fn paths_in_order() {
+        let filesystem = load("$ ls\ndir a\ndir a-b\n$ cd a\n$ ls\ndir c\n");
+        let mut paths = Vec::new();
+        filesystem.walk_paths(|dir, path| {
+            assert_eq!(filesystem.path(dir), path);
+            paths.push(path.to_string());
+        });
+        assert_eq!(paths, ["/", "/a", "/a/c", "/a-b"]);
+    }
+
+    #[test]
+    fn deep_transcript() {
+        let depth = 10_000;
+        let mut input = "$ cd /\n".to_string();
+        for _ in 0..depth {
+            input.push_str("$ cd d\n$ ls\n1 f\n");
+        }
+        let filesystem = load(&input);
+        assert_eq!(filesystem.size(ROOT), depth);
+        assert_eq!(filesystem.dirs().count(), depth + 1);
+    }
+}
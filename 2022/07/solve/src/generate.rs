@@ -0,0 +1,156 @@
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The total size of all files stays in the range in which part two has a solution.
+const MIN_TOTAL: usize = 40_000_001;
+const MAX_TOTAL: usize = 70_000_000;
+
+struct Node {
+    name: String,
+    children: Vec<usize>,
+    files: Vec<(String, usize)>,
+}
+
+fn name(rng: &mut impl Rng) -> String {
+    let len = rng.gen_range(1..=8);
+    (0..len).map(|_| rng.gen_range('a'..='z')).collect()
+}
+
+/// A name that none of `taken` has.
+fn unique_name(rng: &mut impl Rng, taken: &[&str], extension: bool) -> String {
+    loop {
+        let mut name = name(rng);
+        if extension && rng.gen_bool(0.5) {
+            let extension = self::name(rng);
+            name.push('.');
+            name.push_str(&extension[..extension.len().min(3)]);
+        }
+        if !taken.contains(&name.as_str()) {
+            return name;
+        }
+    }
+}
+
+fn taken_names(nodes: &[Node], dir: usize) -> Vec<&str> {
+    let node = &nodes[dir];
+    node.children
+        .iter()
+        .map(|&child| nodes[child].name.as_str())
+        .chain(node.files.iter().map(|(name, _)| name.as_str()))
+        .collect()
+}
+
+fn add_file(rng: &mut impl Rng, nodes: &mut [Node], dir: usize, size: usize) {
+    let name = unique_name(rng, &taken_names(nodes, dir), true);
+    nodes[dir].files.push((name, size));
+}
+
+fn write_listing(rng: &mut impl Rng, nodes: &[Node], dir: usize, out: &mut String) {
+    let node = &nodes[dir];
+    out.push_str("$ ls\n");
+    let mut entries: Vec<String> = node
+        .children
+        .iter()
+        .map(|&child| format!("dir {}", nodes[child].name))
+        .chain(
+            node.files
+                .iter()
+                .map(|(name, size)| format!("{size} {name}")),
+        )
+        .collect();
+    entries.shuffle(rng);
+    for entry in entries {
+        out.push_str(&entry);
+        out.push('\n');
+    }
+
+    for &child in &node.children {
+        out.push_str(&format!("$ cd {}\n", nodes[child].name));
+        write_listing(rng, nodes, child, out);
+        out.push_str("$ cd ..\n");
+    }
+}
+
+/// Generates a terminal transcript that explores a random file system with `size`
+/// directories besides the root.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let mut nodes = vec![Node {
+        name: "/".to_string(),
+        children: Vec::new(),
+        files: Vec::new(),
+    }];
+    for idx in 1..=size {
+        let parent = rng.gen_range(0..idx);
+        let name = unique_name(&mut rng, &taken_names(&nodes, parent), false);
+        nodes.push(Node {
+            name,
+            children: Vec::new(),
+            files: Vec::new(),
+        });
+        nodes[parent].children.push(idx);
+    }
+
+    // mostly small files, so that part one finds some directories
+    let file_count = rng.gen_range(size..=3 * size + 1);
+    let max_small = (MIN_TOTAL / (file_count + 1)).min(300_000);
+    let mut total = 0;
+    for _ in 0..file_count {
+        let dir = rng.gen_range(0..nodes.len());
+        let file_size = rng.gen_range(1..=max_small);
+        add_file(&mut rng, &mut nodes, dir, file_size);
+        total += file_size;
+    }
+
+    // and a few big ones to fill up the disk
+    let mut missing = rng.gen_range(MIN_TOTAL..=MAX_TOTAL) - total;
+    while missing > 0 {
+        let dir = rng.gen_range(0..nodes.len());
+        let file_size = rng
+            .gen_range(1..=missing.min(10_000_000))
+            .max(missing.min(1_000_000));
+        add_file(&mut rng, &mut nodes, dir, file_size);
+        missing -= file_size;
+    }
+
+    let mut transcript = "$ cd /\n".to_string();
+    write_listing(&mut rng, &nodes, 0, &mut transcript);
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::filesystem::Filesystem;
+    use crate::parser::parse;
+    use crate::validate::validate;
+    use crate::Day07;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(7, 20), generate(7, 20));
+        assert_ne!(generate(7, 20), generate(8, 20));
+    }
+
+    #[test]
+    fn generated_transcripts_can_be_solved() {
+        for seed in 0..10 {
+            let filesystem = Day07.parse(&generate(seed, seed as usize * 5)).unwrap();
+            Day07.part1(&filesystem);
+            Day07.part2(&filesystem);
+        }
+    }
+
+    #[test]
+    fn generated_transcripts_are_consistent() {
+        for seed in 0..10 {
+            let commands = parse(&generate(seed, seed as usize * 5)).unwrap();
+            let filesystem = Filesystem::from_commands(&commands);
+            assert_eq!(validate(&commands, &filesystem), []);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use aoc_common::{diagnostic, ParseError, Solution};
+
+use crate::filesystem::Filesystem;
+use crate::parser::parse;
+use crate::planner::Cleanup;
+use crate::validate::validate;
+
+pub mod diff;
+pub mod export;
+pub mod filesystem;
+pub mod generate;
+pub mod parser;
+pub mod planner;
+pub mod report;
+pub mod validate;
+
+pub static INPUT: &str = include_str!("../../input.txt");
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    type Input = Filesystem;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Filesystem, ParseError> {
+        let commands = parse(input)?;
+        let filesystem = Filesystem::from_commands(&commands);
+        for issue in validate(&commands, &filesystem) {
+            diagnostic!("{}: {issue}", issue.severity());
+        }
+        Ok(filesystem)
+    }
+
+    fn part1(&self, filesystem: &Filesystem) -> usize {
+        filesystem
+            .dirs()
+            .map(|dir| filesystem.size(dir))
+            .filter(|s| *s <= 100000)
+            .sum()
+    }
+
+    fn part2(&self, filesystem: &Filesystem) -> usize {
+        let minimum_space_to_free = Cleanup::PUZZLE.space_to_free(filesystem);
+
+        filesystem
+            .dirs()
+            .map(|dir| filesystem.size(dir))
+            .filter(|s| *s >= minimum_space_to_free)
+            .min()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use crate::filesystem::Filesystem;
+    use crate::parser::parse;
+
+    /// The terminal session from the puzzle description.
+    pub(crate) static EX: &str = include_str!("../../example.txt");
+
+    pub(crate) fn load(input: &str) -> Filesystem {
+        Filesystem::from_commands(&parse(input).unwrap())
+    }
+
+    pub(crate) fn example() -> Filesystem {
+        load(EX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::EX;
+
+    #[test]
+    fn example1() {
+        assert_eq!(Day07.part1(&Day07.parse(EX).unwrap()), 95437);
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(Day07.part2(&Day07.parse(EX).unwrap()), 24933642);
+    }
+}
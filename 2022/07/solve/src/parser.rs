@@ -0,0 +1,176 @@
+use aoc_common::ParseError;
+
+/// The directory that `cd` changes into.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Target {
+    Root,
+    Parent,
+    Dir(String),
+}
+
+/// A line of the output of `ls`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Entry {
+    File { name: String, size: usize },
+    Dir { name: String },
+}
+
+/// A command of the terminal session together with its output.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Command {
+    Cd(Target),
+    Ls(Vec<Entry>),
+}
+
+/// A line of the input together with its 1-based line number.
+type Line<'a> = (usize, &'a str);
+
+fn parse_command(line: usize, command: &str) -> Result<Command, ParseError> {
+    // like the names listed by `ls`, the directory of `cd` is the rest of the line, spaces
+    // and all
+    let (name, args) = command.split_once(' ').unwrap_or((command, ""));
+    match (name, args) {
+        ("cd", "/") => Ok(Command::Cd(Target::Root)),
+        ("cd", "..") => Ok(Command::Cd(Target::Parent)),
+        ("cd", "") => Err(ParseError::new(line, 3, "expected a directory after 'cd'")),
+        ("cd", dir) => Ok(Command::Cd(Target::Dir(dir.to_string()))),
+        ("ls", "") => Ok(Command::Ls(Vec::new())),
+        ("ls", _) => Err(ParseError::new(line, 6, "unexpected arguments to 'ls'")),
+        _ => Err(ParseError::new(
+            line,
+            3,
+            format!("unknown command '{name}'"),
+        )),
+    }
+}
+
+fn parse_entry((line, text): Line) -> Result<Entry, ParseError> {
+    let (first, name) = text
+        .split_once(' ')
+        .filter(|(_, name)| !name.is_empty())
+        .ok_or_else(|| ParseError::new(line, 1, "expected a size or 'dir' and a name"))?;
+    if first == "dir" {
+        return Ok(Entry::Dir {
+            name: name.to_string(),
+        });
+    }
+    let size = first
+        .parse()
+        .map_err(|_| ParseError::new(line, 1, format!("invalid size '{first}'")))?;
+    Ok(Entry::File {
+        name: name.to_string(),
+        size,
+    })
+}
+
+/// Parses a terminal session into its commands, attaching the output of `ls` to the command.
+pub fn parse(input: &str) -> Result<Vec<Command>, ParseError> {
+    let mut commands = Vec::new();
+    for (idx, text) in input.lines().enumerate() {
+        let line = (idx + 1, text.trim_end());
+        if line.1.is_empty() {
+            continue;
+        }
+        if let Some(command) = line.1.strip_prefix("$ ") {
+            commands.push(parse_command(line.0, command)?);
+        } else if let Some(Command::Ls(entries)) = commands.last_mut() {
+            entries.push(parse_entry(line)?);
+        } else {
+            return Err(ParseError::new(
+                line.0,
+                1,
+                "output outside of an 'ls' block",
+            ));
+        }
+    }
+    Ok(commands)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::EX;
+
+    fn file(name: &str, size: usize) -> Entry {
+        Entry::File {
+            name: name.to_string(),
+            size,
+        }
+    }
+
+    fn dir(name: &str) -> Entry {
+        Entry::Dir {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn valid_input() {
+        let commands = parse(EX).unwrap();
+        assert_eq!(commands.len(), 10);
+        assert_eq!(commands[0], Command::Cd(Target::Root));
+        assert_eq!(
+            commands[1],
+            Command::Ls(vec![
+                dir("a"),
+                file("b.txt", 14848514),
+                file("c.dat", 8504156),
+                dir("d")
+            ])
+        );
+        assert_eq!(commands[2], Command::Cd(Target::Dir("a".to_string())));
+        assert_eq!(commands[6], Command::Cd(Target::Parent));
+    }
+
+    #[test]
+    fn empty_input_is_ok() {
+        assert_eq!(parse(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn crlf_input() {
+        assert_eq!(parse(&EX.replace('\n', "\r\n")), parse(EX));
+    }
+
+    #[test]
+    fn unknown_command() {
+        let err = parse("$ cd /\n$ rm -rf a\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 3, "unknown command 'rm'"));
+
+        let err = parse("$ cd\n").err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(1, 3, "expected a directory after 'cd'")
+        );
+
+        let err = parse("$ ls -l\n").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 6, "unexpected arguments to 'ls'"));
+    }
+
+    #[test]
+    fn output_outside_of_ls() {
+        let err = parse("dir a\n").err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(1, 1, "output outside of an 'ls' block")
+        );
+
+        let err = parse("$ ls\ndir a\n$ cd a\n123 b\n").err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(4, 1, "output outside of an 'ls' block")
+        );
+    }
+
+    #[test]
+    fn invalid_entry() {
+        let err = parse("$ ls\nabc b.txt\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 1, "invalid size 'abc'"));
+
+        let err = parse("$ ls\n123\n").err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "expected a size or 'dir' and a name")
+        );
+    }
+}
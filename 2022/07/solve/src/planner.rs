@@ -0,0 +1,439 @@
+use crate::filesystem::{DirId, Filesystem, ROOT};
+
+/// Something to delete to free up space.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Deletion {
+    Dir(DirId),
+    File { dir: DirId, name: String },
+}
+
+impl Deletion {
+    pub fn path(&self, filesystem: &Filesystem) -> String {
+        match self {
+            Deletion::Dir(dir) => filesystem.path(*dir),
+            Deletion::File { dir, name } if *dir == ROOT => format!("/{name}"),
+            Deletion::File { dir, name } => format!("{}/{name}", filesystem.path(*dir)),
+        }
+    }
+}
+
+/// The result of [`Cleanup::plan`].
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Plan {
+    /// What to delete, in the order of a depth-first walk over the tree.
+    pub deletions: Vec<(Deletion, usize)>,
+    /// The space that has to be freed.
+    pub needed: usize,
+    /// The space that the deletions free.
+    pub freed: usize,
+}
+
+impl Plan {
+    /// How much more space the plan frees than necessary.
+    pub fn over_freed(&self) -> usize {
+        self.freed - self.needed
+    }
+}
+
+/// The sizes below a limit that non-nested deletions can free, in order, each with the
+/// candidate whose deletion first makes it possible (`usize::MAX` for nothing deleted).
+struct Sums {
+    entries: Vec<(usize, usize)>,
+    limit: usize,
+}
+
+impl Sums {
+    fn new(limit: usize) -> Sums {
+        Sums {
+            entries: vec![(0, usize::MAX)],
+            limit,
+        }
+    }
+
+    /// The smallest sum that is at least `min`.
+    fn first_from(&self, min: usize) -> Option<usize> {
+        let idx = self.entries.partition_point(|&(sum, _)| sum < min);
+        self.entries.get(idx).map(|&(sum, _)| sum)
+    }
+
+    /// The candidate that completes `sum`.
+    fn completed_by(&self, sum: usize) -> usize {
+        let idx = self.entries.partition_point(|&(other, _)| other < sum);
+        self.entries[idx].1
+    }
+
+    /// Adds the sums completed by the candidates that `include` accepts, increased by
+    /// `shift`, and remembers `by` for the new ones. The sums are merged in place from the
+    /// top down, so the ones that are shifted are not overwritten before they are read.
+    fn add_shifted(&mut self, shift: usize, by: usize, include: impl Fn(usize) -> bool) {
+        let len = self.entries.len();
+        let below = self
+            .entries
+            .partition_point(|&(sum, _)| sum + shift < self.limit);
+        let shifted = |idx: usize| {
+            let (sum, completed_by) = self.entries[idx];
+            include(completed_by).then_some(sum + shift)
+        };
+        // count the new sums first to know where the merged sums end
+        let mut added = 0;
+        let mut old = 0;
+        for sum in (0..below).filter_map(shifted) {
+            while old < len && self.entries[old].0 < sum {
+                old += 1;
+            }
+            if old == len || self.entries[old].0 != sum {
+                added += 1;
+            }
+        }
+        if added == 0 {
+            return;
+        }
+
+        self.entries.resize(len + added, (0, 0));
+        let (mut old, mut end) = (len, len + added);
+        for idx in (0..below).rev() {
+            let (sum, completed_by) = self.entries[idx];
+            if !include(completed_by) {
+                continue;
+            }
+            let sum = sum + shift;
+            while self.entries[old - 1].0 > sum {
+                old -= 1;
+                end -= 1;
+                self.entries[end] = self.entries[old];
+            }
+            if self.entries[old - 1].0 != sum {
+                end -= 1;
+                self.entries[end] = (sum, by);
+            }
+        }
+    }
+}
+
+/// A candidate for deletion in depth-first order, with the index just behind its subtree.
+struct Candidate {
+    deletion: Deletion,
+    size: usize,
+    end: usize,
+}
+
+fn candidates(filesystem: &Filesystem, files: bool) -> Vec<Candidate> {
+    let mut candidates: Vec<Candidate> = Vec::new();
+    // directories still to visit, and where their candidate is once it has been added
+    let mut stack: Vec<(DirId, Option<usize>)> = vec![(ROOT, None)];
+    while let Some((dir, added)) = stack.pop() {
+        if let Some(idx) = added {
+            let end = candidates.len();
+            candidates[idx].end = end;
+            continue;
+        }
+        let idx = candidates.len();
+        candidates.push(Candidate {
+            deletion: Deletion::Dir(dir),
+            size: filesystem.size(dir),
+            end: 0,
+        });
+        if files {
+            for (name, size) in filesystem.files(dir) {
+                candidates.push(Candidate {
+                    deletion: Deletion::File {
+                        dir,
+                        name: name.to_string(),
+                    },
+                    size,
+                    end: candidates.len() + 1,
+                });
+            }
+        }
+        stack.push((dir, Some(idx)));
+        stack.extend(
+            filesystem
+                .subdirs(dir)
+                .into_iter()
+                .rev()
+                .map(|subdir| (subdir, None)),
+        );
+    }
+    candidates
+}
+
+/// The disk of the device and how much free space the update needs.
+#[derive(Clone, Copy, Debug)]
+pub struct Cleanup {
+    pub capacity: usize,
+    pub required: usize,
+    /// Whether individual files may be deleted, not only whole directories.
+    pub files: bool,
+}
+
+impl Cleanup {
+    pub const PUZZLE: Cleanup = Cleanup {
+        capacity: 70000000,
+        required: 30000000,
+        files: false,
+    };
+
+    /// How much space has to be freed for the update.
+    pub fn space_to_free(&self, filesystem: &Filesystem) -> usize {
+        let free = self.capacity.saturating_sub(filesystem.size(ROOT));
+        self.required.saturating_sub(free)
+    }
+
+    /// Finds the deletions that free enough space while deleting as little as possible. No
+    /// deletion is inside a deleted directory. Returns `None` if even deleting everything is
+    /// not enough.
+    ///
+    /// Walking the candidates depth-first, each one is either deleted, which skips its
+    /// subtree, or kept. The distinct sizes below the space needed that can be freed before
+    /// each candidate are kept in order, so time grows with the number of candidates times
+    /// the number of such sizes and memory with the number of sizes, however large the sizes
+    /// themselves are. The sizes that deleting a directory adds are added once its subtree has
+    /// been skipped, from those that were there before it. For every size, the deletion that
+    /// first makes it possible is remembered to find the deletions again.
+    pub fn plan(&self, filesystem: &Filesystem) -> Option<Plan> {
+        let needed = self.space_to_free(filesystem);
+        if needed == 0 {
+            return Some(Plan {
+                deletions: Vec::new(),
+                needed,
+                freed: 0,
+            });
+        }
+
+        let candidates = candidates(filesystem, self.files);
+        let mut sums = Sums::new(needed);
+        // deleted directories whose sums are added once their subtrees are done, innermost
+        // last
+        let mut pending: Vec<usize> = Vec::new();
+        // the smallest sum of at least `needed`, and the candidate that completes it
+        let mut best: Option<(usize, usize)> = None;
+        for (idx, candidate) in candidates.iter().enumerate() {
+            while let Some(&prev) = pending.last() {
+                if candidates[prev].end != idx {
+                    break;
+                }
+                pending.pop();
+                // the sums that were there before the directory
+                let before = |by: usize| by == usize::MAX || candidates[by].end <= prev;
+                sums.add_shifted(candidates[prev].size, prev, before);
+            }
+            if let Some(sum) = sums.first_from(needed.saturating_sub(candidate.size)) {
+                let found = (sum + candidate.size, idx);
+                best = Some(best.map_or(found, |best| best.min(found)));
+            }
+            if best.is_some_and(|(freed, _)| freed == needed) {
+                break;
+            }
+            if candidate.size >= needed {
+                continue;
+            }
+            if candidate.end == idx + 1 {
+                sums.add_shifted(candidate.size, idx, |_| true);
+            } else {
+                pending.push(idx);
+            }
+        }
+
+        let (freed, last) = best?;
+        let mut deletions = vec![last];
+        let mut sum = freed - candidates[last].size;
+        while sum > 0 {
+            let prev = sums.completed_by(sum);
+            deletions.push(prev);
+            sum -= candidates[prev].size;
+        }
+        deletions.reverse();
+
+        let deletions = deletions
+            .into_iter()
+            .map(|idx| (candidates[idx].deletion.clone(), candidates[idx].size))
+            .collect();
+        Some(Plan {
+            deletions,
+            needed,
+            freed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::fixtures::{example, load};
+
+    fn paths(filesystem: &Filesystem, plan: &Plan) -> Vec<String> {
+        plan.deletions
+            .iter()
+            .map(|(deletion, _)| deletion.path(filesystem))
+            .collect()
+    }
+
+    #[test]
+    fn example_dirs() {
+        let filesystem = example();
+        let plan = Cleanup::PUZZLE.plan(&filesystem).unwrap();
+        assert_eq!(paths(&filesystem, &plan), ["/d"]);
+        assert_eq!(plan.needed, 8381165);
+        assert_eq!(plan.freed, 24933642);
+    }
+
+    #[test]
+    fn example_files() {
+        let filesystem = example();
+        let plan = Cleanup {
+            files: true,
+            ..Cleanup::PUZZLE
+        }
+        .plan(&filesystem)
+        .unwrap();
+        assert_eq!(paths(&filesystem, &plan), ["/c.dat"]);
+        assert_eq!(plan.over_freed(), 122991);
+    }
+
+    #[test]
+    fn combines_dirs() {
+        let input = "$ ls\ndir a\ndir b\ndir c\n60 x\n\
+                     $ cd a\n$ ls\n40 y\n$ cd ..\n$ cd b\n$ ls\n35 z\n$ cd ..\n$ cd c\n$ ls\n30 w\n";
+        let filesystem = load(input);
+        let cleanup = Cleanup {
+            capacity: 200,
+            required: 135,
+            files: false,
+        };
+        let plan = cleanup.plan(&filesystem).unwrap();
+        assert_eq!(paths(&filesystem, &plan), ["/a", "/b", "/c"]);
+        assert_eq!((plan.needed, plan.freed, plan.over_freed()), (100, 105, 5));
+
+        let plan = Cleanup {
+            files: true,
+            ..cleanup
+        }
+        .plan(&filesystem)
+        .unwrap();
+        assert_eq!(paths(&filesystem, &plan), ["/x", "/a"]);
+        assert_eq!(plan.over_freed(), 0);
+    }
+
+    #[test]
+    fn huge_sizes() {
+        let input = "$ ls\ndir a\n3000000000000 x\n$ cd a\n$ ls\n2000000000000 y\n1 z\n";
+        let filesystem = load(input);
+        let cleanup = Cleanup {
+            capacity: 6000000000000,
+            required: 4000000000000,
+            files: true,
+        };
+        let plan = cleanup.plan(&filesystem).unwrap();
+        assert_eq!(paths(&filesystem, &plan), ["/x", "/a/z"]);
+        assert_eq!((plan.needed, plan.over_freed()), (3000000000001, 0));
+    }
+
+    #[test]
+    fn nothing_to_free() {
+        let filesystem = example();
+        let plan = Cleanup {
+            capacity: 100000000,
+            ..Cleanup::PUZZLE
+        }
+        .plan(&filesystem)
+        .unwrap();
+        assert_eq!(plan.deletions, []);
+        assert_eq!(plan.over_freed(), 0);
+    }
+
+    #[test]
+    fn not_enough_space() {
+        let filesystem = example();
+        let cleanup = Cleanup {
+            capacity: 50000000,
+            required: 60000000,
+            files: false,
+        };
+        assert_eq!(cleanup.plan(&filesystem), None);
+    }
+
+    /// All sets of non-nested deletions below `dir` with the space they free.
+    fn all_plans(filesystem: &Filesystem, dir: DirId, files: bool) -> Vec<(Vec<String>, usize)> {
+        let mut plans = vec![(Vec::new(), 0)];
+        let children = filesystem
+            .subdirs(dir)
+            .into_iter()
+            .map(|subdir| all_plans(filesystem, subdir, files));
+        let files = filesystem
+            .files(dir)
+            .into_iter()
+            .filter(|_| files)
+            .map(|(name, size)| {
+                let path = Deletion::File {
+                    dir,
+                    name: name.to_string(),
+                }
+                .path(filesystem);
+                vec![(Vec::new(), 0), (vec![path], size)]
+            });
+        for options in children.chain(files) {
+            plans = plans
+                .iter()
+                .flat_map(|(paths, freed)| {
+                    options.iter().map(move |(more, size)| {
+                        (paths.iter().chain(more).cloned().collect(), freed + size)
+                    })
+                })
+                .collect();
+        }
+        plans.push((vec![filesystem.path(dir)], filesystem.size(dir)));
+        plans
+    }
+
+    fn random_filesystem(rng: &mut impl Rng) -> Filesystem {
+        let mut input = String::new();
+        for idx in 0..rng.gen_range(1..6) {
+            input.push_str(&format!("$ cd /\n$ cd {}\n$ ls\n", idx % 3));
+            for file in 0..rng.gen_range(0..3) {
+                input.push_str(&format!("{} f{file}\n", rng.gen_range(1..50)));
+            }
+            input.push_str(&format!("$ cd {}\n$ ls\n{} g\n", idx, rng.gen_range(1..50)));
+        }
+        load(&input)
+    }
+
+    #[test]
+    fn plans_are_optimal() {
+        let mut rng = ChaCha8Rng::seed_from_u64(16);
+        for _ in 0..200 {
+            let filesystem = random_filesystem(&mut rng);
+            let total = filesystem.size(ROOT);
+            let cleanup = Cleanup {
+                capacity: total + rng.gen_range(0..20),
+                required: rng.gen_range(1..=total),
+                files: rng.gen_bool(0.5),
+            };
+            let needed = cleanup.space_to_free(&filesystem);
+            let best = all_plans(&filesystem, ROOT, cleanup.files)
+                .into_iter()
+                .map(|(_, freed)| freed)
+                .filter(|&freed| freed >= needed)
+                .min()
+                .unwrap();
+            let plan = cleanup.plan(&filesystem).unwrap();
+            assert_eq!(plan.freed, best);
+            assert_eq!(
+                plan.deletions.iter().map(|(_, size)| size).sum::<usize>(),
+                plan.freed
+            );
+            let paths = paths(&filesystem, &plan);
+            for path in &paths {
+                let dir = format!("{}/", path.trim_end_matches('/'));
+                assert!(
+                    !paths
+                        .iter()
+                        .any(|other| other != path && other.starts_with(&dir)),
+                    "{paths:?}"
+                );
+            }
+        }
+    }
+}
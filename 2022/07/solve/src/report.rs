@@ -0,0 +1,158 @@
+use std::fmt::Write;
+
+use crate::filesystem::{DirId, Filesystem, ROOT};
+
+const UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+
+/// Formats a size in bytes, or like `du -h` with 1024-based units if `human` is set: one
+/// decimal below 10 (`2.5K`), none above (`14M`).
+pub fn format_size(size: usize, human: bool) -> String {
+    if !human || size < 1024 {
+        return size.to_string();
+    }
+    let mut value = size as f64;
+    let mut unit = "";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    if value < 9.95 {
+        format!("{value:.1}{unit}")
+    } else {
+        format!("{value:.0}{unit}")
+    }
+}
+
+/// Every directory with its total size, like the output of `du` but parents first and with
+/// the subdirectories of each directory sorted by name.
+pub fn du(filesystem: &Filesystem, human: bool) -> String {
+    let mut out = String::new();
+    filesystem.walk_paths(|dir, path| {
+        writeln!(out, "{}\t{path}", format_size(filesystem.size(dir), human)).unwrap();
+    });
+    out
+}
+
+/// The `count` largest directories with their sizes, largest first.
+pub fn largest(filesystem: &Filesystem, count: usize, human: bool) -> String {
+    filesystem
+        .largest(count)
+        .into_iter()
+        .map(|dir| {
+            format!(
+                "{}\t{}\n",
+                format_size(filesystem.size(dir), human),
+                filesystem.path(dir)
+            )
+        })
+        .collect()
+}
+
+enum Item<'a> {
+    Dir(DirId),
+    File(&'a str, usize),
+}
+
+/// The whole tree in the format used by the puzzle description, with the entries of each
+/// directory sorted by name.
+pub fn tree(filesystem: &Filesystem, human: bool) -> String {
+    let mut out = String::new();
+    // an explicit stack, as transcripts can be nested arbitrarily deep
+    let mut stack = vec![(0, Item::Dir(ROOT))];
+    while let Some((depth, item)) = stack.pop() {
+        let indent = "  ".repeat(depth);
+        match item {
+            Item::Dir(dir) => {
+                let name = if dir == ROOT {
+                    "/"
+                } else {
+                    filesystem.name(dir)
+                };
+                let size = format_size(filesystem.size(dir), human);
+                writeln!(out, "{indent}- {name} (dir, size={size})").unwrap();
+
+                let mut children: Vec<(&str, Item)> = filesystem
+                    .subdirs(dir)
+                    .into_iter()
+                    .map(|subdir| (filesystem.name(subdir), Item::Dir(subdir)))
+                    .chain(
+                        filesystem
+                            .files(dir)
+                            .into_iter()
+                            .map(|(name, size)| (name, Item::File(name, size))),
+                    )
+                    .collect();
+                children.sort_by_key(|(name, _)| *name);
+                stack.extend(
+                    children
+                        .into_iter()
+                        .rev()
+                        .map(|(_, child)| (depth + 1, child)),
+                );
+            }
+            Item::File(name, size) => {
+                let size = format_size(size, human);
+                writeln!(out, "{indent}- {name} (file, size={size})").unwrap();
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::example;
+
+    #[test]
+    fn human_readable_sizes() {
+        assert_eq!(format_size(584, true), "584");
+        assert_eq!(format_size(2557, true), "2.5K");
+        assert_eq!(format_size(94853, true), "93K");
+        assert_eq!(format_size(14848514, true), "14M");
+        assert_eq!(format_size(10239, true), "10K");
+        assert_eq!(format_size(3 << 30, true), "3.0G");
+        assert_eq!(format_size(14848514, false), "14848514");
+    }
+
+    #[test]
+    fn du_report() {
+        assert_eq!(
+            du(&example(), false),
+            "48381165\t/\n94853\t/a\n584\t/a/e\n24933642\t/d\n"
+        );
+        assert_eq!(
+            du(&example(), true),
+            "46M\t/\n93K\t/a\n584\t/a/e\n24M\t/d\n"
+        );
+    }
+
+    #[test]
+    fn largest_report() {
+        assert_eq!(largest(&example(), 2, false), "48381165\t/\n24933642\t/d\n");
+    }
+
+    #[test]
+    fn tree_report() {
+        let expected = "\
+- / (dir, size=48381165)
+  - a (dir, size=94853)
+    - e (dir, size=584)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir, size=24933642)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+";
+        assert_eq!(tree(&example(), false), expected);
+    }
+}
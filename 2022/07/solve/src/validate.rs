@@ -0,0 +1,299 @@
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+
+use crate::filesystem::{DirId, Filesystem, ROOT};
+use crate::parser::{Command, Entry, Target};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Severity {
+    /// The transcript is incomplete, so the sizes may be too small.
+    Warning,
+    /// The transcript contradicts itself, so the file system cannot be trusted.
+    Error,
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error => write!(f, "error"),
+        }
+    }
+}
+
+/// An inconsistency in a terminal session. Paths are absolute, like `/a/e`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Issue {
+    /// A file was listed twice with different sizes.
+    ConflictingSize {
+        file: String,
+        first: usize,
+        second: usize,
+    },
+    /// A directory was listed twice with different entries.
+    ConflictingListing { dir: String },
+    /// `cd` changed into a directory that no listing contains.
+    PhantomDir { dir: String },
+    /// `cd ..` was used in the root directory.
+    ParentOfRoot,
+    /// A directory was listed by its parent but never itself.
+    Unexplored { dir: String },
+}
+
+impl Issue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::ConflictingSize { .. }
+            | Issue::ConflictingListing { .. }
+            | Issue::ParentOfRoot => Severity::Error,
+            Issue::PhantomDir { .. } | Issue::Unexplored { .. } => Severity::Warning,
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Issue::ConflictingSize {
+                file,
+                first,
+                second,
+            } => {
+                write!(f, "{file} is listed with sizes {first} and {second}")
+            }
+            Issue::ConflictingListing { dir } => {
+                write!(f, "{dir} is listed twice with different entries")
+            }
+            Issue::PhantomDir { dir } => write!(f, "{dir} is entered but never listed"),
+            Issue::ParentOfRoot => write!(f, "'cd ..' in the root directory"),
+            Issue::Unexplored { dir } => write!(f, "{dir} is listed but never explored"),
+        }
+    }
+}
+
+/// The entries of a listing by name, so that listings can be compared regardless of order.
+type Listing = BTreeMap<String, Option<usize>>;
+
+fn listing(entries: &[Entry]) -> Listing {
+    entries
+        .iter()
+        .map(|entry| match entry {
+            Entry::File { name, size } => (name.clone(), Some(*size)),
+            Entry::Dir { name } => (name.clone(), None),
+        })
+        .collect()
+}
+
+/// What was seen of every directory of the [`Filesystem`] built from the same commands.
+struct Seen<'a> {
+    filesystem: &'a Filesystem,
+    /// The first listing of each directory.
+    listings: Vec<Option<Listing>>,
+    entered: Vec<bool>,
+}
+
+impl Seen<'_> {
+    /// Whether the first listing of the parent contains `dir` as a directory.
+    fn listed_by_parent(&self, dir: DirId) -> bool {
+        let Some(parent) = self.filesystem.parent(dir) else {
+            return false;
+        };
+        self.listings[parent]
+            .as_ref()
+            .is_some_and(|listing| listing.get(self.filesystem.name(dir)) == Some(&None))
+    }
+
+    fn child_path(&self, dir: DirId, name: &str) -> String {
+        if dir == ROOT {
+            format!("/{name}")
+        } else {
+            format!("{}/{name}", self.filesystem.path(dir))
+        }
+    }
+
+    fn compare_listings(&self, dir: DirId, second: &Listing, issues: &mut Vec<Issue>) {
+        let first = self.listings[dir]
+            .as_ref()
+            .expect("only listed directories are compared");
+        let mut conflicting_entries = first.len() != second.len();
+        for (name, entry) in second {
+            match (first.get(name), entry) {
+                (Some(Some(first)), Some(second)) if first != second => {
+                    issues.push(Issue::ConflictingSize {
+                        file: self.child_path(dir, name),
+                        first: *first,
+                        second: *second,
+                    });
+                }
+                (Some(first), second) if first.is_some() == second.is_some() => {}
+                _ => conflicting_entries = true,
+            }
+        }
+        if conflicting_entries {
+            issues.push(Issue::ConflictingListing {
+                dir: self.filesystem.path(dir),
+            });
+        }
+    }
+}
+
+/// Replays the commands on `filesystem`, which was built from them, and reports everything
+/// that does not add up, in the order found. Whether entered directories are listed and
+/// listed directories are explored can only be told at the end, so these issues are
+/// reported last, in the order the directories were first seen.
+pub fn validate(commands: &[Command], filesystem: &Filesystem) -> Vec<Issue> {
+    let mut issues = Vec::new();
+    let dirs = filesystem.dirs().count();
+    let mut seen = Seen {
+        filesystem,
+        listings: vec![None; dirs],
+        entered: vec![false; dirs],
+    };
+    let mut cwd = ROOT;
+
+    for command in commands {
+        match command {
+            Command::Cd(Target::Root) => cwd = ROOT,
+            Command::Cd(Target::Parent) => match filesystem.parent(cwd) {
+                Some(parent) => cwd = parent,
+                None => issues.push(Issue::ParentOfRoot),
+            },
+            Command::Cd(Target::Dir(dir)) => {
+                cwd = filesystem
+                    .subdir(cwd, dir)
+                    .expect("the file system has every directory of the commands");
+                seen.entered[cwd] = true;
+            }
+            Command::Ls(entries) => {
+                let entries = listing(entries);
+                if seen.listings[cwd].is_some() {
+                    seen.compare_listings(cwd, &entries, &mut issues);
+                } else {
+                    seen.listings[cwd] = Some(entries);
+                }
+            }
+        }
+    }
+
+    for dir in filesystem.dirs() {
+        if dir != ROOT && seen.entered[dir] && !seen.listed_by_parent(dir) {
+            issues.push(Issue::PhantomDir {
+                dir: filesystem.path(dir),
+            });
+        }
+    }
+    for dir in filesystem.dirs() {
+        if seen.listings[dir].is_none() && seen.listed_by_parent(dir) {
+            issues.push(Issue::Unexplored {
+                dir: filesystem.path(dir),
+            });
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::EX;
+    use crate::parser::parse;
+
+    fn issues(input: &str) -> Vec<Issue> {
+        let commands = parse(input).unwrap();
+        validate(&commands, &Filesystem::from_commands(&commands))
+    }
+
+    #[test]
+    fn consistent_session() {
+        assert_eq!(issues(EX), []);
+
+        // listing a directory again is fine as long as nothing changed
+        let input = "$ cd /\n$ ls\n12 b\ndir a\n$ cd a\n$ ls\n$ cd /\n$ ls\ndir a\n12 b\n";
+        assert_eq!(issues(input), []);
+    }
+
+    #[test]
+    fn conflicting_listings() {
+        let input = "$ cd /\n$ ls\ndir a\n$ cd a\n$ ls\n12 b\n34 c\n$ ls\n56 b\n34 c\n";
+        assert_eq!(
+            issues(input),
+            [Issue::ConflictingSize {
+                file: "/a/b".to_string(),
+                first: 12,
+                second: 56
+            }]
+        );
+
+        let input = "$ ls\n12 b\n$ ls\n12 b\ndir c\n$ ls\ndir b\n";
+        assert_eq!(
+            issues(input),
+            [
+                Issue::ConflictingListing {
+                    dir: "/".to_string()
+                },
+                Issue::ConflictingListing {
+                    dir: "/".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn phantom_dirs() {
+        let input = "$ cd /\n$ cd a\n$ ls\n$ cd /\n$ ls\ndir b\n$ cd c\n$ cd /\n$ cd c\n";
+        assert_eq!(
+            issues(input),
+            [
+                Issue::PhantomDir {
+                    dir: "/a".to_string()
+                },
+                Issue::PhantomDir {
+                    dir: "/c".to_string()
+                },
+                Issue::Unexplored {
+                    dir: "/b".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn entered_before_listed() {
+        let input = "$ cd /\n$ cd a\n$ ls\n1 x\n$ cd ..\n$ ls\ndir a\n";
+        assert_eq!(issues(input), []);
+    }
+
+    #[test]
+    fn deep_session() {
+        let depth = 20_000;
+        let mut input = "$ cd /\n".to_string();
+        for _ in 0..depth {
+            input.push_str("$ ls\ndir d\n1 f\n$ cd d\n");
+        }
+        input.push_str("$ ls\n");
+        assert_eq!(issues(&input), []);
+    }
+
+    #[test]
+    fn parent_of_root() {
+        assert_eq!(issues("$ cd /\n$ cd ..\n$ ls\n"), [Issue::ParentOfRoot]);
+    }
+
+    #[test]
+    fn severity_and_display() {
+        let issue = Issue::ConflictingSize {
+            file: "/a/b".to_string(),
+            first: 1,
+            second: 2,
+        };
+        assert_eq!(issue.severity(), Severity::Error);
+        assert_eq!(issue.to_string(), "/a/b is listed with sizes 1 and 2");
+
+        let issue = Issue::Unexplored {
+            dir: "/a".to_string(),
+        };
+        assert_eq!(issue.severity(), Severity::Warning);
+        assert_eq!(issue.to_string(), "/a is listed but never explored");
+    }
+}
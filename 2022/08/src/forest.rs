@@ -0,0 +1,398 @@
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use aoc_grid::{Grid, Pos};
+
+use crate::sight::{line_end, lines_of_sight, sweep_line, Compass, Sight};
+use crate::TreeHeights;
+
+/// Whether a tree can be seen from outside the forest and how scenic it is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TreeStatus {
+    pub visible: bool,
+    pub scenic_score: usize,
+}
+
+/// A tree whose status was changed by an update. `before` is `None` for newly planted trees.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StatusChange {
+    pub pos: Pos,
+    pub before: Option<TreeStatus>,
+    pub after: TreeStatus,
+}
+
+/// Why a [`Forest`] could not be changed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UpdateError {
+    NoTree(Pos),
+    NoRow(usize),
+    /// Trees are 0 to 9 high, like in the puzzle input.
+    Height(u8),
+    /// A new row has to be as wide as the forest.
+    Width {
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for UpdateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateError::NoTree((row, col)) => write!(f, "there is no tree at {row},{col}"),
+            UpdateError::NoRow(row) => write!(f, "row {row} is outside of the forest"),
+            UpdateError::Height(height) => write!(f, "invalid height {height}"),
+            UpdateError::Width { expected, found } => {
+                write!(f, "the new row has {found} trees instead of {expected}")
+            }
+        }
+    }
+}
+
+impl Error for UpdateError {}
+
+/// A forest that can be changed while keeping the number of visible trees and the highest
+/// scenic score up to date.
+///
+/// Changing a tree only changes what the trees on the lines through it see, so only these
+/// lines are looked along again.
+pub struct Forest {
+    tree_heights: TreeHeights,
+    directions: Vec<Compass>,
+    /// What every tree sees in each of the directions.
+    sights: Vec<Grid<Sight>>,
+    visible: usize,
+    /// How many trees have each scenic score.
+    scores: BTreeMap<usize, usize>,
+}
+
+impl Forest {
+    /// Takes visibility and scenic scores over the given directions.
+    pub fn new(tree_heights: TreeHeights, directions: &[Compass]) -> Forest {
+        let sights = directions
+            .iter()
+            .map(|&direction| lines_of_sight(&tree_heights, direction))
+            .collect();
+        let mut forest = Forest {
+            tree_heights,
+            directions: directions.to_vec(),
+            sights,
+            visible: 0,
+            scores: BTreeMap::new(),
+        };
+        for pos in forest.tree_heights.positions() {
+            forest.count(forest.status(pos), 1);
+        }
+        forest
+    }
+
+    pub fn tree_heights(&self) -> &TreeHeights {
+        &self.tree_heights
+    }
+
+    pub fn status(&self, pos: Pos) -> TreeStatus {
+        TreeStatus {
+            visible: self.sights.iter().any(|sights| sights[pos].visible),
+            scenic_score: self
+                .sights
+                .iter()
+                .map(|sights| sights[pos].distance)
+                .product(),
+        }
+    }
+
+    /// The number of trees that can be seen from outside the forest.
+    pub fn visible_count(&self) -> usize {
+        self.visible
+    }
+
+    pub fn max_scenic_score(&self) -> usize {
+        self.scores.keys().next_back().copied().unwrap_or(0)
+    }
+
+    /// Adds a tree with `status` to the totals if `count` is 1, or removes it if it is -1.
+    fn count(&mut self, status: TreeStatus, count: isize) {
+        const COUNTED: &str = "only trees that were counted are removed";
+        self.visible = self
+            .visible
+            .checked_add_signed(count * status.visible as isize)
+            .expect(COUNTED);
+        let trees = self.scores.entry(status.scenic_score).or_default();
+        *trees = trees.checked_add_signed(count).expect(COUNTED);
+        if *trees == 0 {
+            self.scores.remove(&status.scenic_score);
+        }
+    }
+
+    /// The trees on the lines that end at `ends`, given with the index of their direction.
+    fn trees_on_lines(&self, ends: &[(usize, Pos)]) -> Vec<Pos> {
+        let mut trees: Vec<Pos> = ends
+            .iter()
+            .flat_map(|&(idx, end)| {
+                let backward = self.directions[idx].opposite().offset();
+                std::iter::successors(Some(end), move |&pos| {
+                    self.tree_heights.offset(pos, backward)
+                })
+            })
+            .collect();
+        trees.sort_unstable();
+        trees.dedup();
+        trees
+    }
+
+    /// Looks along the lines that end at `ends` again and counts the `trees` on them, which
+    /// were not counted with their status from `before`.
+    fn recount(
+        &mut self,
+        ends: &[(usize, Pos)],
+        trees: Vec<Pos>,
+        before: Vec<Option<TreeStatus>>,
+    ) -> Vec<StatusChange> {
+        let mut stack = Vec::new();
+        for &(idx, end) in ends {
+            let direction = self.directions[idx];
+            sweep_line(
+                &self.tree_heights,
+                end,
+                direction,
+                &mut stack,
+                &mut self.sights[idx],
+            );
+        }
+
+        let mut changes = Vec::new();
+        for (pos, before) in trees.into_iter().zip(before) {
+            let after = self.status(pos);
+            self.count(after, 1);
+            if before != Some(after) {
+                changes.push(StatusChange { pos, before, after });
+            }
+        }
+        changes
+    }
+
+    /// Changes the height of the tree at `pos` to a height from 0 to 9, returning the trees
+    /// whose status changed.
+    pub fn set_height(&mut self, pos: Pos, height: u8) -> Result<Vec<StatusChange>, UpdateError> {
+        if self.tree_heights.get(pos).is_none() {
+            return Err(UpdateError::NoTree(pos));
+        }
+        check_height(height)?;
+        // every line through `pos`, by its end in each of the directions
+        let ends: Vec<(usize, Pos)> = self
+            .directions
+            .iter()
+            .enumerate()
+            .map(|(idx, &direction)| (idx, line_end(&self.tree_heights, pos, direction)))
+            .collect();
+        let trees = self.trees_on_lines(&ends);
+
+        let before: Vec<Option<TreeStatus>> =
+            trees.iter().map(|&pos| Some(self.status(pos))).collect();
+        for status in before.iter().flatten() {
+            self.count(*status, -1);
+        }
+        self.tree_heights[pos] = height;
+        Ok(self.recount(&ends, trees, before))
+    }
+
+    /// Cuts the tree at `pos` down to a stump of height 0.
+    pub fn cut_down(&mut self, pos: Pos) -> Result<Vec<StatusChange>, UpdateError> {
+        self.set_height(pos, 0)
+    }
+
+    /// Plants a new row of trees from 0 to 9 high so that it becomes row `row`, moving the
+    /// rows from there on down. Only the lines through the new row are looked along again;
+    /// the others just move with their rows. The changes are reported at the positions after
+    /// planting.
+    pub fn plant_row(
+        &mut self,
+        row: usize,
+        heights: Vec<u8>,
+    ) -> Result<Vec<StatusChange>, UpdateError> {
+        if row > self.tree_heights.height() {
+            return Err(UpdateError::NoRow(row));
+        }
+        let width = self.tree_heights.width();
+        if self.tree_heights.height() > 0 && heights.len() != width {
+            return Err(UpdateError::Width {
+                expected: width,
+                found: heights.len(),
+            });
+        }
+        heights
+            .iter()
+            .try_for_each(|&height| check_height(height))?;
+
+        let width = heights.len();
+        self.tree_heights.insert_row(row, heights);
+        for sights in &mut self.sights {
+            sights.insert_row(row, vec![Sight::default(); width]);
+        }
+        // every line through the new row, by its end in each of the directions
+        let mut ends: Vec<(usize, Pos)> = (0..width)
+            .flat_map(|col| {
+                let tree_heights = &self.tree_heights;
+                self.directions
+                    .iter()
+                    .enumerate()
+                    .map(move |(idx, &direction)| {
+                        (idx, line_end(tree_heights, (row, col), direction))
+                    })
+            })
+            .collect();
+        ends.sort_unstable();
+        ends.dedup();
+        let trees = self.trees_on_lines(&ends);
+
+        // the other trees have moved along with what they see, so their status still holds
+        let before: Vec<Option<TreeStatus>> = trees
+            .iter()
+            .map(|&pos| (pos.0 != row).then(|| self.status(pos)))
+            .collect();
+        for status in before.iter().flatten() {
+            self.count(*status, -1);
+        }
+        Ok(self.recount(&ends, trees, before))
+    }
+}
+
+fn check_height(height: u8) -> Result<(), UpdateError> {
+    if height > 9 {
+        return Err(UpdateError::Height(height));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::fixtures::{example, random_forest};
+
+    fn statuses(forest: &Forest) -> Vec<TreeStatus> {
+        forest
+            .tree_heights
+            .positions()
+            .map(|pos| forest.status(pos))
+            .collect()
+    }
+
+    #[test]
+    fn example_updates() {
+        let mut forest = Forest::new(example(), &Compass::ORTHOGONAL);
+        assert_eq!((forest.visible_count(), forest.max_scenic_score()), (21, 8));
+
+        // the stump is hidden, but opens the view to the west for the tree next to it
+        let changes = forest.cut_down((3, 2)).unwrap();
+        assert_eq!((forest.visible_count(), forest.max_scenic_score()), (21, 9));
+        let next = changes.iter().find(|change| change.pos == (3, 3)).unwrap();
+        assert_eq!(
+            next.before,
+            Some(TreeStatus {
+                visible: false,
+                scenic_score: 3
+            })
+        );
+        assert_eq!(
+            next.after,
+            TreeStatus {
+                visible: true,
+                scenic_score: 9
+            }
+        );
+        assert!(changes
+            .iter()
+            .all(|change| change.pos.0 == 3 || change.pos.1 == 2));
+
+        // setting the same height again changes nothing
+        assert_eq!(forest.set_height((3, 2), 0), Ok(vec![]));
+
+        let changes = forest.plant_row(0, vec![9, 9, 9, 9, 9]).unwrap();
+        assert_eq!(forest.tree_heights().height(), 6);
+        assert_eq!(
+            changes
+                .iter()
+                .filter(|change| change.before.is_none())
+                .count(),
+            5
+        );
+        assert_eq!(forest.visible_count(), 24);
+        let fresh = Forest::new(forest.tree_heights.clone(), &Compass::ORTHOGONAL);
+        assert_eq!(statuses(&forest), statuses(&fresh));
+    }
+
+    #[test]
+    fn plant_into_empty_forest() {
+        let mut forest = Forest::new(Grid::from_rows(vec![]), &Compass::ORTHOGONAL);
+        assert_eq!((forest.visible_count(), forest.max_scenic_score()), (0, 0));
+        assert_eq!(forest.plant_row(0, vec![1, 2, 1]).unwrap().len(), 3);
+        assert_eq!(forest.visible_count(), 3);
+    }
+
+    #[test]
+    fn invalid_updates() {
+        let mut forest = Forest::new(example(), &Compass::ORTHOGONAL);
+        assert_eq!(
+            forest.set_height((5, 0), 1),
+            Err(UpdateError::NoTree((5, 0)))
+        );
+        assert_eq!(forest.set_height((0, 0), 10), Err(UpdateError::Height(10)));
+        assert_eq!(forest.plant_row(6, vec![1; 5]), Err(UpdateError::NoRow(6)));
+        assert_eq!(
+            forest.plant_row(0, vec![1; 4]),
+            Err(UpdateError::Width {
+                expected: 5,
+                found: 4
+            })
+        );
+        assert_eq!(
+            forest.plant_row(5, vec![1, 2, 30, 4, 5]),
+            Err(UpdateError::Height(30))
+        );
+        assert_eq!(forest.tree_heights(), &example());
+        assert_eq!((forest.visible_count(), forest.max_scenic_score()), (21, 8));
+    }
+
+    #[test]
+    fn updates_match_recomputing() {
+        let mut rng = ChaCha8Rng::seed_from_u64(24);
+        for directions in [&Compass::ORTHOGONAL[..], &Compass::ALL[..]] {
+            for _ in 0..20 {
+                let mut forest = Forest::new(random_forest(&mut rng, 8), directions);
+                let width = forest.tree_heights.width();
+                for _ in 0..20 {
+                    let before = statuses(&forest);
+                    let old_heights = forest.tree_heights.clone();
+                    let changes = if rng.gen_bool(0.1) {
+                        let row = rng.gen_range(0..=old_heights.height());
+                        let heights = (0..width).map(|_| rng.gen_range(0..=9)).collect();
+                        forest.plant_row(row, heights).unwrap()
+                    } else {
+                        let row = rng.gen_range(0..old_heights.height());
+                        let pos = (row, rng.gen_range(0..width));
+                        forest.set_height(pos, rng.gen_range(0..=9)).unwrap()
+                    };
+
+                    let fresh = Forest::new(forest.tree_heights.clone(), directions);
+                    assert_eq!(statuses(&forest), statuses(&fresh));
+                    assert_eq!(forest.visible_count(), fresh.visible_count());
+                    assert_eq!(forest.max_scenic_score(), fresh.max_scenic_score());
+                    if old_heights.height() == forest.tree_heights.height() {
+                        let changed: Vec<Pos> = forest
+                            .tree_heights
+                            .positions()
+                            .zip(before.iter().zip(statuses(&forest)))
+                            .filter(|(_, (before, after))| *before != after)
+                            .map(|(pos, _)| pos)
+                            .collect();
+                        let reported: Vec<Pos> = changes.iter().map(|change| change.pos).collect();
+                        assert_eq!(reported, changed);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Generates a square forest with `size` rows of `size` trees each.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut forest = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        forest.extend((0..size).map(|_| char::from(b'0' + rng.gen_range(0..=9))));
+        forest.push('\n');
+    }
+    forest
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::Day08;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(8, 10), generate(8, 10));
+        assert_ne!(generate(8, 10), generate(9, 10));
+    }
+
+    #[test]
+    fn generated_forests_can_be_solved() {
+        for seed in 0..10 {
+            let size = 3 + seed as usize * 4;
+            let forest = Day08.parse(&generate(seed, size)).unwrap();
+            assert_eq!((forest.width(), forest.height()), (size, size));
+            Day08.part1(&forest);
+            Day08.part2(&forest);
+        }
+    }
+}
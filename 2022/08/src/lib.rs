@@ -0,0 +1,322 @@
+use aoc_common::{ParseError, Solution};
+use aoc_grid::Grid;
+
+use crate::sight::{best_scenic_spot, count_visible, lines_of_sight, Compass};
+
+pub mod forest;
+pub mod generate;
+pub mod render;
+pub mod report;
+pub mod sight;
+pub mod viewshed;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+pub type TreeHeights = Grid<u8>;
+
+/// How many trees can be seen from a tree in each direction, up to the edge or the first
+/// tree that is at least as tall.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct ViewingDistances {
+    pub up: usize,
+    pub down: usize,
+    pub left: usize,
+    pub right: usize,
+}
+
+impl ViewingDistances {
+    pub fn scenic_score(&self) -> usize {
+        self.up * self.down * self.left * self.right
+    }
+}
+
+/// The viewing distances of all trees, in time linear in the number of trees.
+pub fn viewing_distances(tree_heights: &TreeHeights) -> Grid<ViewingDistances> {
+    let [up, right, down, left] =
+        Compass::ORTHOGONAL.map(|direction| lines_of_sight(tree_heights, direction));
+    let (width, height) = (tree_heights.width(), tree_heights.height());
+    let mut distances = Grid::new(width, height, ViewingDistances::default());
+    for pos in tree_heights.positions() {
+        distances[pos] = ViewingDistances {
+            up: up[pos].distance,
+            down: down[pos].distance,
+            left: left[pos].distance,
+            right: right[pos].distance,
+        };
+    }
+    distances
+}
+
+fn max_scenic_score(tree_heights: &TreeHeights) -> usize {
+    best_scenic_spot(tree_heights, &Compass::ORTHOGONAL).map_or(0, |(_, score)| score)
+}
+
+/// Reads one height from 0 to 9 per tree and one row of trees per line. All rows must be
+/// equally long; lines may end in CRLF.
+fn load_tree_heights(input: &str) -> Result<TreeHeights, ParseError> {
+    let tree_heights = Grid::parse(input, |_, c| c.to_digit(10).map(|d| d as u8))?;
+    if tree_heights.is_empty() {
+        return Err(ParseError::at_column(1, "the forest has no trees"));
+    }
+    Ok(tree_heights)
+}
+
+fn count_visible_trees(tree_heights: &TreeHeights) -> usize {
+    count_visible(tree_heights, &Compass::ORTHOGONAL)
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = TreeHeights;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<TreeHeights, ParseError> {
+        load_tree_heights(input)
+    }
+
+    fn part1(&self, tree_heights: &TreeHeights) -> usize {
+        count_visible_trees(tree_heights)
+    }
+
+    fn part2(&self, tree_heights: &TreeHeights) -> usize {
+        max_scenic_score(tree_heights)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use aoc_grid::Grid;
+    use rand::Rng;
+
+    use crate::TreeHeights;
+
+    /// The forest from the puzzle description.
+    pub(crate) fn example() -> TreeHeights {
+        Grid::from_rows(vec![
+            vec![3, 0, 3, 7, 3],
+            vec![2, 5, 5, 1, 2],
+            vec![6, 5, 3, 3, 2],
+            vec![3, 3, 5, 4, 9],
+            vec![3, 5, 3, 9, 0],
+        ])
+    }
+
+    /// A forest less than `max_size` trees wide and high. The trees are at most as tall as a
+    /// random limit, so that trees of the same height are common in low forests.
+    pub(crate) fn random_forest(rng: &mut impl Rng, max_size: usize) -> TreeHeights {
+        let (width, height) = (rng.gen_range(1..max_size), rng.gen_range(1..max_size));
+        let max_height = rng.gen_range(0..=9);
+        let rows = (0..height)
+            .map(|_| (0..width).map(|_| rng.gen_range(0..=max_height)).collect())
+            .collect();
+        Grid::from_rows(rows)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::fixtures::{example, random_forest};
+
+    // The straightforward scans towards the edge, as a reference for `viewing_distances`.
+
+    fn view_distance_left(tree_heights: &TreeHeights, posx: usize, posy: usize) -> usize {
+        if posy == 0 {
+            return 0;
+        }
+
+        let refheight = tree_heights[(posx, posy)];
+        let mut distance = 0;
+        for y in (0..posy).rev() {
+            let h = tree_heights[(posx, y)];
+            distance += 1;
+            if h >= refheight {
+                break;
+            }
+        }
+
+        distance
+    }
+
+    fn view_distance_right(tree_heights: &TreeHeights, posx: usize, posy: usize) -> usize {
+        let refheight = tree_heights[(posx, posy)];
+        let mut distance = 0;
+        for y in posy + 1..tree_heights.width() {
+            let h = tree_heights[(posx, y)];
+            distance += 1;
+            if h >= refheight {
+                break;
+            }
+        }
+
+        distance
+    }
+
+    fn view_distance_up(tree_heights: &TreeHeights, posx: usize, posy: usize) -> usize {
+        if posx == 0 {
+            return 0;
+        }
+
+        let refheight = tree_heights[(posx, posy)];
+        let mut distance = 0;
+        for x in (0..posx).rev() {
+            let h = tree_heights[(x, posy)];
+            distance += 1;
+            if h >= refheight {
+                break;
+            }
+        }
+
+        distance
+    }
+
+    fn view_distance_down(tree_heights: &TreeHeights, posx: usize, posy: usize) -> usize {
+        let refheight = tree_heights[(posx, posy)];
+        let mut distance = 0;
+        for x in posx + 1..tree_heights.height() {
+            let h = tree_heights[(x, posy)];
+            distance += 1;
+            if h >= refheight {
+                break;
+            }
+        }
+
+        distance
+    }
+
+    fn scenic_score(tree_heights: &TreeHeights, posx: usize, posy: usize) -> usize {
+        let distance_left = view_distance_left(tree_heights, posx, posy);
+        let distance_right = view_distance_right(tree_heights, posx, posy);
+        let distance_up = view_distance_up(tree_heights, posx, posy);
+        let distance_down = view_distance_down(tree_heights, posx, posy);
+        distance_left * distance_right * distance_up * distance_down
+    }
+
+    #[test]
+    fn test_view_distance_up() {
+        assert_eq!(view_distance_up(&example(), 3, 2), 2);
+        assert_eq!(view_distance_down(&example(), 3, 2), 1);
+        assert_eq!(view_distance_left(&example(), 3, 2), 2);
+        assert_eq!(view_distance_right(&example(), 3, 2), 2);
+    }
+
+    #[test]
+    fn test_scenic_score() {
+        assert_eq!(scenic_score(&example(), 3, 2), 8);
+        assert_eq!(max_scenic_score(&example()), 8);
+    }
+
+    #[test]
+    fn load_example() {
+        let input = "30373\n25512\n65332\n33549\n35390\n";
+        assert_eq!(load_tree_heights(input).unwrap(), example());
+        assert_eq!(
+            load_tree_heights(&input.replace('\n', "\r\n")).unwrap(),
+            example()
+        );
+        assert_eq!(load_tree_heights(input.trim_end()).unwrap(), example());
+    }
+
+    #[test]
+    fn load_invalid() {
+        let err = load_tree_heights("303\n2a5\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 2));
+        assert_eq!(err.message, "invalid character 'a'");
+        let err = load_tree_heights("30373\n2551\n65332\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 5));
+        assert_eq!(err.message, "expected 5 columns but found 4");
+        let err = load_tree_heights("303 \n").unwrap_err();
+        assert_eq!(err.message, "invalid character ' '");
+        assert!(load_tree_heights("").is_err());
+        assert!(load_tree_heights("\r\n\n").is_err());
+    }
+
+    #[test]
+    fn degenerate_forests() {
+        for (input, visible, scenic) in [
+            ("7\n", 1, 0),
+            ("12321\n", 5, 0),
+            ("1\n2\n3\n2\n1\n", 5, 0),
+            ("12\n34\n", 4, 0),
+            ("555\n555\n555\n", 8, 1),
+        ] {
+            let tree_heights = load_tree_heights(input).unwrap();
+            assert_eq!(Day08.part1(&tree_heights), visible, "{input:?}");
+            assert_eq!(Day08.part2(&tree_heights), scenic, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_count_visible_trees() {
+        assert_eq!(count_visible_trees(&example()), 21);
+        assert_eq!(
+            count_visible_trees(&Grid::from_rows(vec![vec![1, 2, 3, 2]])),
+            4
+        );
+        assert_eq!(count_visible_trees(&Grid::from_rows(vec![vec![5]; 3])), 3);
+    }
+
+    #[test]
+    fn test_viewing_distances() {
+        let distances = viewing_distances(&example());
+        assert_eq!(
+            distances[(3, 2)],
+            ViewingDistances {
+                up: 2,
+                down: 1,
+                left: 2,
+                right: 2
+            }
+        );
+        assert_eq!(
+            distances[(1, 2)],
+            ViewingDistances {
+                up: 1,
+                down: 2,
+                left: 1,
+                right: 2
+            }
+        );
+        assert_eq!(
+            distances[(0, 0)],
+            ViewingDistances {
+                up: 0,
+                down: 2,
+                left: 0,
+                right: 2
+            }
+        );
+    }
+
+    #[test]
+    fn viewing_distances_match_reference() {
+        let mut rng = ChaCha8Rng::seed_from_u64(8);
+        for _ in 0..100 {
+            let tree_heights = random_forest(&mut rng, 12);
+            let distances = viewing_distances(&tree_heights);
+            for (x, y) in tree_heights.positions() {
+                let expected = ViewingDistances {
+                    up: view_distance_up(&tree_heights, x, y),
+                    down: view_distance_down(&tree_heights, x, y),
+                    left: view_distance_left(&tree_heights, x, y),
+                    right: view_distance_right(&tree_heights, x, y),
+                };
+                assert_eq!(
+                    distances[(x, y)],
+                    expected,
+                    "at {:?} in\n{tree_heights}",
+                    (x, y)
+                );
+                assert_eq!(
+                    distances[(x, y)].scenic_score(),
+                    scenic_score(&tree_heights, x, y)
+                );
+            }
+        }
+    }
+}
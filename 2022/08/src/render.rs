@@ -0,0 +1,148 @@
+use std::fmt::Write;
+
+use aoc_grid::Grid;
+
+use crate::sight::{most_scenic, scenic_scores, visibility, Compass};
+use crate::TreeHeights;
+
+pub type Rgb = [u8; 3];
+
+/// The colour of the most scenic tree on the scenic score heatmap.
+pub const HIGHLIGHT: Rgb = [0, 200, 255];
+
+const HIDDEN: Rgb = [45, 55, 45];
+const VISIBLE: Rgb = [240, 200, 60];
+
+/// What the colours of a rendering show.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Layer {
+    /// Short trees dark, tall trees light green.
+    Heights,
+    /// Trees that can be seen from outside the forest in yellow, hidden trees dark.
+    Visibility,
+    /// The scenic scores on a logarithmic scale from black over red and yellow to white,
+    /// with the most scenic tree in blue.
+    Scenic,
+}
+
+fn mix(from: Rgb, to: Rgb, t: f64) -> Rgb {
+    let channel = |c| (from[c] as f64 + (to[c] as f64 - from[c] as f64) * t).round() as u8;
+    [channel(0), channel(1), channel(2)]
+}
+
+/// Picks a colour on the heatmap ramp for `t` between 0 and 1.
+fn heat(t: f64) -> Rgb {
+    const RAMP: [Rgb; 4] = [[0, 0, 0], [200, 30, 0], [255, 210, 0], [255, 255, 255]];
+    let scaled = t.clamp(0.0, 1.0) * (RAMP.len() - 1) as f64;
+    let idx = (scaled as usize).min(RAMP.len() - 2);
+    mix(RAMP[idx], RAMP[idx + 1], scaled - idx as f64)
+}
+
+/// The colour of every tree, with visibility and scenic scores taken over the given
+/// directions.
+pub fn colors(tree_heights: &TreeHeights, layer: Layer, directions: &[Compass]) -> Grid<Rgb> {
+    match layer {
+        Layer::Heights => {
+            tree_heights.map(|&height| mix([20, 45, 20], [160, 235, 130], height as f64 / 9.0))
+        }
+        Layer::Visibility => {
+            visibility(tree_heights, directions)
+                .map(|&visible| if visible { VISIBLE } else { HIDDEN })
+        }
+        Layer::Scenic => {
+            let scores = scenic_scores(tree_heights, directions);
+            let Some((best, max)) = most_scenic(&scores) else {
+                return Grid::new(0, 0, HIDDEN);
+            };
+            let scale = (max as f64).ln_1p().max(f64::MIN_POSITIVE);
+            let mut colors = scores.map(|&score| heat((score as f64).ln_1p() / scale));
+            colors[best] = HIGHLIGHT;
+            colors
+        }
+    }
+}
+
+/// Encodes the colours as a binary PPM image, drawing every tree as a square of `scale` by
+/// `scale` pixels.
+pub fn ppm(colors: &Grid<Rgb>, scale: usize) -> Vec<u8> {
+    let (width, height) = (colors.width() * scale, colors.height() * scale);
+    let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+    image.reserve(width * height * 3);
+    for row in colors.rows() {
+        for _ in 0..scale {
+            for color in row {
+                for _ in 0..scale {
+                    image.extend_from_slice(color);
+                }
+            }
+        }
+    }
+    image
+}
+
+/// Draws the heights as digits on the colours, using 24-bit ANSI escape codes.
+pub fn ansi(tree_heights: &TreeHeights, colors: &Grid<Rgb>) -> String {
+    let mut out = String::new();
+    for (heights, colors) in tree_heights.rows().zip(colors.rows()) {
+        for (height, &[r, g, b]) in heights.iter().zip(colors) {
+            // dark digits on light backgrounds and the other way round
+            let luma = 299 * r as u32 + 587 * g as u32 + 114 * b as u32;
+            let fg = if luma > 128_000 { 30 } else { 97 };
+            write!(out, "\x1b[{fg};48;2;{r};{g};{b}m{height}").unwrap();
+        }
+        out.push_str("\x1b[0m\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::example;
+
+    #[test]
+    fn heat_ramp() {
+        assert_eq!(heat(0.0), [0, 0, 0]);
+        assert_eq!(heat(1.0), [255, 255, 255]);
+        assert_eq!(heat(0.5), [228, 120, 0]);
+    }
+
+    #[test]
+    fn layers() {
+        let tree_heights = example();
+        let heights = colors(&tree_heights, Layer::Heights, &Compass::ORTHOGONAL);
+        assert_eq!(heights[(1, 0)], heights[(1, 4)]);
+        assert!(heights[(4, 3)][1] > heights[(0, 1)][1]);
+
+        let visible = colors(&tree_heights, Layer::Visibility, &Compass::ORTHOGONAL);
+        assert_eq!(visible[(1, 1)], VISIBLE);
+        assert_eq!(visible[(2, 2)], HIDDEN);
+
+        let scenic = colors(&tree_heights, Layer::Scenic, &Compass::ORTHOGONAL);
+        assert_eq!(scenic[(3, 2)], HIGHLIGHT);
+        assert_eq!(scenic[(0, 0)], [0, 0, 0]);
+        assert!(scenic[(2, 1)][1] > scenic[(1, 1)][1]);
+    }
+
+    #[test]
+    fn ppm_image() {
+        let colors = Grid::from_rows(vec![vec![[1, 2, 3], [4, 5, 6]]]);
+        let image = ppm(&colors, 2);
+        let header = b"P6\n4 2\n255\n";
+        assert_eq!(&image[..header.len()], header);
+        let pixels = &image[header.len()..];
+        assert_eq!(pixels.len(), 4 * 2 * 3);
+        assert_eq!(&pixels[..12], [1, 2, 3, 1, 2, 3, 4, 5, 6, 4, 5, 6]);
+        assert_eq!(&pixels[12..], &pixels[..12]);
+    }
+
+    #[test]
+    fn terminal_output() {
+        let tree_heights = Grid::from_rows(vec![vec![0, 9]]);
+        let colors = colors(&tree_heights, Layer::Visibility, &Compass::ORTHOGONAL);
+        assert_eq!(
+            ansi(&tree_heights, &colors),
+            "\x1b[30;48;2;240;200;60m0\x1b[30;48;2;240;200;60m9\x1b[0m\n"
+        );
+    }
+}
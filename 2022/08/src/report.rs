@@ -0,0 +1,158 @@
+use std::cmp::Reverse;
+
+use aoc_grid::{Grid, Pos};
+
+use crate::sight::{lines_of_sight, scenic_scores, Compass};
+use crate::{viewing_distances, TreeHeights, ViewingDistances};
+
+/// Everything about one tree.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TreeReport {
+    pub height: u8,
+    /// The directions from which the tree can be seen from outside the forest.
+    pub visible_from: Vec<Compass>,
+    pub distances: ViewingDistances,
+    /// The product of the viewing distances in the directions the report was made for.
+    pub scenic_score: usize,
+}
+
+impl TreeReport {
+    pub fn is_visible(&self) -> bool {
+        !self.visible_from.is_empty()
+    }
+
+    /// Formats the report like
+    /// `(3, 2) height=5 visible=S,W up=2 down=1 left=2 right=2 score=8`.
+    pub fn format(&self, (row, col): Pos) -> String {
+        let visible = if self.is_visible() {
+            let names: Vec<String> = self.visible_from.iter().map(Compass::to_string).collect();
+            names.join(",")
+        } else {
+            "none".to_string()
+        };
+        let ViewingDistances {
+            up,
+            down,
+            left,
+            right,
+        } = self.distances;
+        format!(
+            "({row}, {col}) height={} visible={visible} up={up} down={down} left={left} \
+             right={right} score={}",
+            self.height, self.scenic_score
+        )
+    }
+}
+
+/// Reports on every tree, with the visibility and the scenic score taken over the given
+/// directions. The viewing distances are always the four along the rows and columns.
+pub fn tree_reports(tree_heights: &TreeHeights, directions: &[Compass]) -> Grid<TreeReport> {
+    let distances = viewing_distances(tree_heights);
+    let scores = scenic_scores(tree_heights, directions);
+    let sights: Vec<_> = directions
+        .iter()
+        .map(|&direction| (direction, lines_of_sight(tree_heights, direction)))
+        .collect();
+    let rows = (0..tree_heights.height())
+        .map(|row| {
+            (0..tree_heights.width())
+                .map(|col| TreeReport {
+                    height: tree_heights[(row, col)],
+                    visible_from: sights
+                        .iter()
+                        .filter(|(_, sight)| sight[(row, col)].visible)
+                        .map(|&(direction, _)| direction)
+                        .collect(),
+                    distances: distances[(row, col)],
+                    scenic_score: scores[(row, col)],
+                })
+                .collect()
+        })
+        .collect();
+    Grid::from_rows(rows)
+}
+
+/// The `count` trees with the highest scenic scores over the given directions, highest
+/// first and in reading order among equal scores.
+pub fn most_scenic(
+    tree_heights: &TreeHeights,
+    directions: &[Compass],
+    count: usize,
+) -> Vec<(Pos, usize)> {
+    let mut trees: Vec<(Pos, usize)> = scenic_scores(tree_heights, directions)
+        .iter()
+        .map(|(pos, &score)| (pos, score))
+        .collect();
+    trees.sort_by_key(|&(pos, score)| (Reverse(score), pos));
+    trees.truncate(count);
+    trees
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::example;
+
+    #[test]
+    fn example_reports() {
+        let reports = tree_reports(&example(), &Compass::ORTHOGONAL);
+        assert_eq!(
+            reports[(3, 2)],
+            TreeReport {
+                height: 5,
+                visible_from: vec![Compass::S, Compass::W],
+                distances: ViewingDistances {
+                    up: 2,
+                    down: 1,
+                    left: 2,
+                    right: 2
+                },
+                scenic_score: 8,
+            }
+        );
+        assert_eq!(reports[(1, 1)].visible_from, [Compass::N, Compass::W]);
+        assert!(!reports[(2, 2)].is_visible());
+        assert_eq!(
+            reports
+                .iter()
+                .filter(|(_, report)| report.is_visible())
+                .count(),
+            21
+        );
+        assert_eq!(
+            reports[(0, 0)].format((0, 0)),
+            "(0, 0) height=3 visible=N,W up=0 down=2 left=0 right=2 score=0"
+        );
+        assert_eq!(
+            reports[(2, 2)].format((2, 2)),
+            "(2, 2) height=3 visible=none up=1 down=1 left=1 right=1 score=1"
+        );
+    }
+
+    #[test]
+    fn diagonal_reports() {
+        let reports = tree_reports(&example(), &Compass::ALL);
+        assert_eq!(
+            reports[(3, 3)].visible_from,
+            [Compass::NE, Compass::SE, Compass::SW]
+        );
+        assert_eq!(reports[(2, 2)].scenic_score, 2);
+        let distances = ViewingDistances {
+            up: 1,
+            down: 1,
+            left: 1,
+            right: 1,
+        };
+        assert_eq!(reports[(2, 2)].distances, distances);
+    }
+
+    #[test]
+    fn top_scenic_trees() {
+        assert_eq!(
+            most_scenic(&example(), &Compass::ORTHOGONAL, 3),
+            [((3, 2), 8), ((2, 1), 6), ((1, 2), 4)]
+        );
+        assert_eq!(most_scenic(&example(), &Compass::ORTHOGONAL, 100).len(), 25);
+        assert_eq!(most_scenic(&example(), &Compass::ORTHOGONAL, 0), []);
+    }
+}
@@ -0,0 +1,264 @@
+use std::fmt::{Display, Formatter};
+
+use aoc_grid::{Grid, Pos, NEIGHBORS8};
+
+use crate::TreeHeights;
+
+/// One of the eight compass directions, with north at the top of the map. The directions go
+/// clockwise from north, like [`NEIGHBORS8`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Compass {
+    N,
+    NE,
+    E,
+    SE,
+    S,
+    SW,
+    W,
+    NW,
+}
+
+impl Compass {
+    pub const ALL: [Compass; 8] = [
+        Compass::N,
+        Compass::NE,
+        Compass::E,
+        Compass::SE,
+        Compass::S,
+        Compass::SW,
+        Compass::W,
+        Compass::NW,
+    ];
+
+    /// The directions along the rows and columns, which are the ones the puzzle looks in.
+    pub const ORTHOGONAL: [Compass; 4] = [Compass::N, Compass::E, Compass::S, Compass::W];
+
+    /// The step in this direction as a `(row, column)` offset.
+    pub fn offset(self) -> (isize, isize) {
+        NEIGHBORS8[self as usize]
+    }
+
+    pub fn opposite(self) -> Compass {
+        Compass::ALL[(self as usize + 4) % 8]
+    }
+}
+
+impl Display for Compass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// What a tree sees when looking in one direction.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub struct Sight {
+    /// The number of trees up to the edge or the first tree that is at least as tall.
+    pub distance: usize,
+    /// Whether all trees up to the edge are shorter, so the tree can be seen from outside.
+    pub visible: bool,
+}
+
+/// Looks in `direction` from every tree, in time linear in the number of trees.
+pub fn lines_of_sight(tree_heights: &TreeHeights, direction: Compass) -> Grid<Sight> {
+    let mut sights = Grid::new(
+        tree_heights.width(),
+        tree_heights.height(),
+        Sight::default(),
+    );
+    let mut stack = Vec::new();
+    let line_ends = tree_heights
+        .positions()
+        .filter(|&pos| tree_heights.offset(pos, direction.offset()).is_none());
+    for end in line_ends {
+        sweep_line(tree_heights, end, direction, &mut stack, &mut sights);
+    }
+    sights
+}
+
+/// The last tree of the line through `pos` in `direction`, the one at the edge.
+pub(crate) fn line_end(tree_heights: &TreeHeights, pos: Pos, direction: Compass) -> Pos {
+    let mut end = pos;
+    while let Some(next) = tree_heights.offset(end, direction.offset()) {
+        end = next;
+    }
+    end
+}
+
+/// Looks in `direction` from every tree of the line that ends at `end`, storing what the
+/// trees see in `sights`.
+///
+/// The line is walked backwards, starting at the edge. A stack holds the trees seen so far
+/// that are not hidden behind a taller tree closer to the current one, so every tree is
+/// pushed and popped at most once.
+pub(crate) fn sweep_line(
+    tree_heights: &TreeHeights,
+    end: Pos,
+    direction: Compass,
+    stack: &mut Vec<(usize, u8)>,
+    sights: &mut Grid<Sight>,
+) {
+    let backward = direction.opposite().offset();
+    stack.clear();
+    let line = std::iter::successors(Some(end), |&pos| tree_heights.offset(pos, backward));
+    for (idx, pos) in line.enumerate() {
+        let height = tree_heights[pos];
+        while stack.last().is_some_and(|&(_, blocker)| blocker < height) {
+            stack.pop();
+        }
+        sights[pos] = match stack.last() {
+            Some(&(blocker, _)) => Sight {
+                distance: idx - blocker,
+                visible: false,
+            },
+            None => Sight {
+                distance: idx,
+                visible: true,
+            },
+        };
+        stack.push((idx, height));
+    }
+}
+
+/// The scenic score of every tree when looking in the given directions, i.e. the product of
+/// its viewing distances.
+pub fn scenic_scores(tree_heights: &TreeHeights, directions: &[Compass]) -> Grid<usize> {
+    let mut scores = Grid::new(tree_heights.width(), tree_heights.height(), 1);
+    for &direction in directions {
+        for (pos, sight) in lines_of_sight(tree_heights, direction).iter() {
+            scores[pos] *= sight.distance;
+        }
+    }
+    scores
+}
+
+/// Whether every tree can be seen from outside the forest from at least one of the given
+/// directions.
+pub fn visibility(tree_heights: &TreeHeights, directions: &[Compass]) -> Grid<bool> {
+    let mut visible = Grid::new(tree_heights.width(), tree_heights.height(), false);
+    for &direction in directions {
+        for (pos, sight) in lines_of_sight(tree_heights, direction).iter() {
+            visible[pos] |= sight.visible;
+        }
+    }
+    visible
+}
+
+/// The number of trees that can be seen from outside from one of the given directions.
+pub fn count_visible(tree_heights: &TreeHeights, directions: &[Compass]) -> usize {
+    visibility(tree_heights, directions)
+        .iter()
+        .filter(|(_, &visible)| visible)
+        .count()
+}
+
+/// The highest scenic score when looking in the given directions, and where it is.
+pub fn best_scenic_spot(
+    tree_heights: &TreeHeights,
+    directions: &[Compass],
+) -> Option<(Pos, usize)> {
+    most_scenic(&scenic_scores(tree_heights, directions))
+}
+
+/// The highest of the scenic `scores` and where it is; the first tree in reading order wins
+/// ties.
+pub fn most_scenic(scores: &Grid<usize>) -> Option<(Pos, usize)> {
+    scores
+        .iter()
+        .map(|(pos, &score)| (pos, score))
+        .max_by_key(|&(pos, score)| (score, std::cmp::Reverse(pos)))
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_grid::NEIGHBORS4;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::fixtures::{example, random_forest};
+
+    /// Walks from `pos` in `direction` tree by tree.
+    fn walk(tree_heights: &TreeHeights, pos: Pos, direction: Compass) -> Sight {
+        let mut sight = Sight {
+            distance: 0,
+            visible: true,
+        };
+        let mut current = pos;
+        while let Some(next) = tree_heights.offset(current, direction.offset()) {
+            sight.distance += 1;
+            if tree_heights[next] >= tree_heights[pos] {
+                sight.visible = false;
+                break;
+            }
+            current = next;
+        }
+        sight
+    }
+
+    #[test]
+    fn offsets() {
+        assert_eq!(Compass::N.offset(), (-1, 0));
+        assert_eq!(Compass::SW.offset(), (1, -1));
+        let orthogonal = Compass::ORTHOGONAL.map(Compass::offset);
+        assert_eq!(orthogonal, NEIGHBORS4);
+    }
+
+    #[test]
+    fn opposite_directions() {
+        assert_eq!(Compass::N.opposite(), Compass::S);
+        assert_eq!(Compass::SW.opposite(), Compass::NE);
+        for direction in Compass::ALL {
+            let (drow, dcol) = direction.offset();
+            assert_eq!(direction.opposite().offset(), (-drow, -dcol));
+        }
+    }
+
+    #[test]
+    fn example_orthogonal() {
+        let tree_heights = example();
+        assert_eq!(count_visible(&tree_heights, &Compass::ORTHOGONAL), 21);
+        assert_eq!(
+            best_scenic_spot(&tree_heights, &Compass::ORTHOGONAL),
+            Some(((3, 2), 8))
+        );
+    }
+
+    #[test]
+    fn example_diagonal() {
+        let tree_heights = example();
+        let sights = lines_of_sight(&tree_heights, Compass::NE);
+        assert_eq!(
+            sights[(3, 2)],
+            Sight {
+                distance: 2,
+                visible: true
+            }
+        );
+        assert_eq!(
+            sights[(2, 2)],
+            Sight {
+                distance: 2,
+                visible: false
+            }
+        );
+        // the tree at (3, 3) can also be seen diagonally, past the short trees to its north-east
+        assert_eq!(count_visible(&tree_heights, &Compass::ALL), 22);
+        assert_eq!(scenic_scores(&tree_heights, &Compass::ALL)[(2, 2)], 2);
+    }
+
+    #[test]
+    fn lines_of_sight_match_walks() {
+        let mut rng = ChaCha8Rng::seed_from_u64(20);
+        for _ in 0..100 {
+            let tree_heights = random_forest(&mut rng, 10);
+            for direction in Compass::ALL {
+                let sights = lines_of_sight(&tree_heights, direction);
+                for pos in tree_heights.positions() {
+                    let expected = walk(&tree_heights, pos, direction);
+                    let context = format!("{direction:?} at {pos:?} in\n{tree_heights}");
+                    assert_eq!(sights[pos], expected, "{context}");
+                }
+            }
+        }
+    }
+}
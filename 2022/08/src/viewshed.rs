@@ -0,0 +1,250 @@
+use aoc_grid::{Grid, Pos};
+
+use crate::TreeHeights;
+
+/// Someone looking at the forest from above the centre of a tree's square.
+///
+/// Every tree is a column filling its unit square, up to its height. The observer's own
+/// square is ignored when looking at other trees.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Observer {
+    pub pos: Pos,
+    pub altitude: f64,
+}
+
+impl Observer {
+    /// Someone standing on top of the tree at `pos`.
+    pub fn on_tree(tree_heights: &TreeHeights, pos: Pos) -> Observer {
+        Observer {
+            pos,
+            altitude: tree_heights[pos] as f64,
+        }
+    }
+
+    /// Someone floating at `altitude` above the ground at `pos`.
+    pub fn above(pos: Pos, altitude: f64) -> Observer {
+        Observer { pos, altitude }
+    }
+}
+
+/// Whether the observer can see the top of the tree at `target`.
+///
+/// The sight line runs from the observer to the centre of the target's top and is walked
+/// square by square. As the distance from the observer cancels out of all slope comparisons,
+/// they are made along the fraction `t` of the way to the target instead: the target is hidden
+/// by a tree of height `h` if `h - altitude >= (target - altitude) * t` somewhere inside that
+/// tree's square, and the worst place is where the sight line enters the square for trees that
+/// reach above the observer and where it leaves for lower ones. A sight line that only touches
+/// the corner of a square is not blocked by it, so diagonals behave as they do in
+/// [`crate::sight`].
+pub fn can_see(tree_heights: &TreeHeights, observer: Observer, target: Pos) -> bool {
+    let (from, z) = (observer.pos, observer.altitude);
+    if target == from {
+        return z > tree_heights[target] as f64;
+    }
+    let (drow, dcol) = (
+        target.0 as isize - from.0 as isize,
+        target.1 as isize - from.1 as isize,
+    );
+    let (step_row, step_col) = (drow.signum(), dcol.signum());
+    let (len_row, len_col) = (drow.unsigned_abs(), dcol.unsigned_abs());
+    let rise = tree_heights[target] as f64 - z;
+
+    // The sight line crosses its `i`-th row boundary at t = (2i + 1) / (2 * len_row), and
+    // likewise for columns; the crossings are compared exactly by cross-multiplying.
+    let (mut rows_crossed, mut cols_crossed) = (0, 0);
+    let mut pos = from;
+    let mut enter = 0.0;
+    loop {
+        let row_crossing = (2 * rows_crossed + 1) * len_col;
+        let col_crossing = (2 * cols_crossed + 1) * len_row;
+        let next_row = len_row > 0 && (len_col == 0 || row_crossing <= col_crossing);
+        let next_col = len_col > 0 && (len_row == 0 || col_crossing <= row_crossing);
+        let exit = if next_row {
+            (2 * rows_crossed + 1) as f64 / (2 * len_row) as f64
+        } else {
+            (2 * cols_crossed + 1) as f64 / (2 * len_col) as f64
+        };
+
+        if pos != from {
+            let height = tree_heights[pos] as f64 - z;
+            let t = if height >= 0.0 { enter } else { exit };
+            if height >= rise * t {
+                return false;
+            }
+        }
+
+        if next_row {
+            pos.0 = pos.0.wrapping_add_signed(step_row);
+            rows_crossed += 1;
+        }
+        if next_col {
+            pos.1 = pos.1.wrapping_add_signed(step_col);
+            cols_crossed += 1;
+        }
+        if pos == target {
+            return true;
+        }
+        enter = exit;
+    }
+}
+
+/// Which trees the observer can see.
+pub fn viewshed(tree_heights: &TreeHeights, observer: Observer) -> Grid<bool> {
+    let mut visible = Grid::new(tree_heights.width(), tree_heights.height(), false);
+    for pos in tree_heights.positions() {
+        visible[pos] = can_see(tree_heights, observer, pos);
+    }
+    visible
+}
+
+/// Draws the viewshed as the heights of the visible trees, with `.` for hidden trees and `@`
+/// for the observer.
+pub fn format_viewshed(
+    tree_heights: &TreeHeights,
+    observer: Observer,
+    visible: &Grid<bool>,
+) -> String {
+    let mut out = String::new();
+    for (row, heights) in tree_heights.rows().enumerate() {
+        for (col, height) in heights.iter().enumerate() {
+            out.push(match (row, col) {
+                pos if pos == observer.pos => '@',
+                pos if visible[pos] => char::from(b'0' + height),
+                _ => '.',
+            });
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{Rng, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use super::*;
+    use crate::fixtures::{example, random_forest};
+
+    /// Checks every square against the sight line, by clipping the line to the square.
+    fn can_see_by_clipping(tree_heights: &TreeHeights, observer: Observer, target: Pos) -> bool {
+        let z = observer.altitude;
+        if target == observer.pos {
+            return z > tree_heights[target] as f64;
+        }
+        let start = (observer.pos.0 as f64 + 0.5, observer.pos.1 as f64 + 0.5);
+        let delta = (
+            target.0 as f64 - observer.pos.0 as f64,
+            target.1 as f64 - observer.pos.1 as f64,
+        );
+        let rise = tree_heights[target] as f64 - z;
+        tree_heights.positions().all(|pos| {
+            if pos == observer.pos || pos == target {
+                return true;
+            }
+            let (mut enter, mut exit) = (0.0f64, 1.0f64);
+            for (origin, delta, low) in [(start.0, delta.0, pos.0), (start.1, delta.1, pos.1)] {
+                let (low, high) = (low as f64, low as f64 + 1.0);
+                if delta == 0.0 {
+                    if origin <= low || origin >= high {
+                        return true;
+                    }
+                } else {
+                    let (a, b) = ((low - origin) / delta, (high - origin) / delta);
+                    enter = enter.max(a.min(b));
+                    exit = exit.min(a.max(b));
+                }
+            }
+            // a sight line that misses the square or only touches it is not blocked
+            if exit - enter <= 1e-9 {
+                return true;
+            }
+            let lowest = (z + rise * enter).min(z + rise * exit);
+            (tree_heights[pos] as f64) < lowest
+        })
+    }
+
+    #[test]
+    fn along_a_row() {
+        let row = Grid::from_rows(vec![vec![5, 3, 4, 3, 5, 9]]);
+        let observer = Observer::on_tree(&row, (0, 0));
+        assert_eq!(
+            format_viewshed(&row, observer, &viewshed(&row, observer)),
+            "@34.59\n"
+        );
+        // unlike in the puzzle, a tree can be seen behind one just as tall when looking down
+        // on both
+        let row = Grid::from_rows(vec![vec![5, 3, 4, 4]]);
+        let observer = Observer::on_tree(&row, (0, 0));
+        assert_eq!(
+            format_viewshed(&row, observer, &viewshed(&row, observer)),
+            "@344\n"
+        );
+    }
+
+    #[test]
+    fn equal_heights_block() {
+        let flat = Grid::new(5, 5, 4);
+        let observer = Observer::on_tree(&flat, (2, 2));
+        let expected = "\
+.....
+.444.
+.4@4.
+.444.
+.....
+";
+        assert_eq!(
+            format_viewshed(&flat, observer, &viewshed(&flat, observer)),
+            expected
+        );
+        // from a little higher up, all of it can be seen
+        let observer = Observer::above((2, 2), 4.5);
+        assert!(viewshed(&flat, observer)
+            .iter()
+            .all(|(_, &visible)| visible));
+    }
+
+    #[test]
+    fn example_views() {
+        let tree_heights = example();
+        let observer = Observer::on_tree(&tree_heights, (3, 2));
+        let expected = "\
+...73
+..5..
+6533.
+33@49
+.539.
+";
+        let visible = viewshed(&tree_heights, observer);
+        assert_eq!(format_viewshed(&tree_heights, observer, &visible), expected);
+
+        let high = Observer::above((2, 2), 100.0);
+        assert!(viewshed(&tree_heights, high)
+            .iter()
+            .all(|(_, &visible)| visible));
+    }
+
+    #[test]
+    fn matches_clipping() {
+        let mut rng = ChaCha8Rng::seed_from_u64(23);
+        for _ in 0..50 {
+            let tree_heights = random_forest(&mut rng, 9);
+            let (width, height) = (tree_heights.width(), tree_heights.height());
+            let pos = (rng.gen_range(0..height), rng.gen_range(0..width));
+            let observers = [
+                Observer::on_tree(&tree_heights, pos),
+                Observer::above(pos, rng.gen_range(0.0..15.0)),
+            ];
+            for observer in observers {
+                for target in tree_heights.positions() {
+                    assert_eq!(
+                        can_see(&tree_heights, observer, target),
+                        can_see_by_clipping(&tree_heights, observer, target),
+                        "{observer:?} looking at {target:?} in\n{tree_heights}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,37 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Generates `size` motions of the head of the rope.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut motions = String::new();
+    for _ in 0..size {
+        let direction = ["U", "D", "L", "R"][rng.gen_range(0..4)];
+        let distance = rng.gen_range(1..=20);
+        motions.push_str(&format!("{direction} {distance}\n"));
+    }
+    motions
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::Day09;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(9, 50), generate(9, 50));
+        assert_ne!(generate(9, 50), generate(10, 50));
+    }
+
+    #[test]
+    fn generated_motions_can_be_solved() {
+        for seed in 0..10 {
+            let motions = Day09.parse(&generate(seed, seed as usize * 20)).unwrap();
+            assert_eq!(motions.len(), seed as usize * 20);
+            assert!(Day09.part1(&motions) >= Day09.part2(&motions));
+        }
+    }
+}
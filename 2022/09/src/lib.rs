@@ -0,0 +1,299 @@
+use std::collections::HashSet;
+use std::str::FromStr;
+
+use aoc_common::{parse_lines, ParseError, Solution};
+use aoc_geometry::{Direction, Point};
+
+pub mod generate;
+
+pub static INPUT: &str = include_str!("../input.txt");
+
+fn parse_direction(s: &str) -> Result<Direction, ParseError> {
+    match s {
+        "R" => Ok(Direction::Right),
+        "D" => Ok(Direction::Down),
+        "L" => Ok(Direction::Left),
+        "U" => Ok(Direction::Up),
+        _ => Err(ParseError::at_column(1, format!("invalid direction '{s}'"))),
+    }
+}
+
+#[derive(Debug)]
+pub struct Motion {
+    direction: Direction,
+    distance: usize,
+}
+
+impl FromStr for Motion {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Motion, ParseError> {
+        let (direction, distance) = s
+            .split_once(' ')
+            .ok_or_else(|| ParseError::at_column(1, "expected a direction and a distance"))?;
+        Ok(Motion {
+            direction: parse_direction(direction)?,
+            distance: distance.parse().map_err(|_| {
+                ParseError::at_column(
+                    direction.len() + 2,
+                    format!("invalid distance '{distance}'"),
+                )
+            })?,
+        })
+    }
+}
+
+pub type Motions = Vec<Motion>;
+
+type Position = Point<isize>;
+
+/// Moves `knot` one step towards `other` unless they are touching, returning whether it moved.
+fn follow(knot: &mut Position, other: &Position) -> bool {
+    if knot.chebyshev_distance(*other) < 2 {
+        // no need to move
+        return false;
+    }
+
+    // move one step in the direction of other
+    *knot += (*other - *knot).signum();
+
+    true
+}
+
+fn load_input(input: &str) -> Result<Motions, ParseError> {
+    parse_lines(input, str::parse)
+}
+
+struct RopeSimulator {
+    tail_positions: HashSet<Position>,
+    knots: Vec<Position>,
+}
+
+impl RopeSimulator {
+    fn new(knot_count: usize) -> RopeSimulator {
+        RopeSimulator {
+            tail_positions: HashSet::from([Position::ORIGIN]),
+            knots: vec![Position::ORIGIN; knot_count],
+        }
+    }
+
+    fn number_of_tail_positions(&self) -> usize {
+        self.tail_positions.len()
+    }
+
+    fn step(&mut self, direction: Direction) {
+        // first move the head
+        self.knots[0] += direction.offset();
+
+        let mut last_knot = self.knots[0];
+        for knot in &mut self.knots[1..] {
+            if !follow(knot, &last_knot) {
+                break;
+            }
+            last_knot = *knot;
+        }
+
+        // record the position of the tail
+        self.tail_positions.insert(*self.knots.last().unwrap());
+    }
+
+    fn simulate(&mut self, motion: &Motion) -> &mut Self {
+        for _ in 0..motion.distance {
+            self.step(motion.direction);
+        }
+        self
+    }
+}
+
+fn count_tail_positions(motions: &Motions, knot_count: usize) -> usize {
+    motions
+        .iter()
+        .fold(&mut RopeSimulator::new(knot_count), |simulator, motion| {
+            simulator.simulate(motion)
+        })
+        .number_of_tail_positions()
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    type Input = Motions;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Motions, ParseError> {
+        load_input(input)
+    }
+
+    fn part1(&self, motions: &Motions) -> usize {
+        count_tail_positions(motions, 2)
+    }
+
+    fn part2(&self, motions: &Motions) -> usize {
+        count_tail_positions(motions, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EX1: &str = include_str!("../example1.txt");
+    static EX2: &str = include_str!("../example2.txt");
+
+    #[test]
+    fn test_position_hash() {
+        let mut positions: HashSet<Position> = HashSet::from([Position::new(0, 0)]);
+        positions.insert(Position::new(0, 0));
+        positions.insert(Position::new(0, 1));
+        positions.insert(Position::new(1, 0));
+        positions.insert(Position::new(1, 1));
+        positions.insert(Position::new(0, 0));
+        positions.insert(Position::new(1, 0));
+        assert_eq!(positions.len(), 4);
+    }
+
+    #[test]
+    fn test_load() {
+        let input = load_input(EX1).unwrap();
+        assert_eq!(input.len(), 8);
+        assert_eq!(input[0].direction, Direction::Right);
+        assert_eq!(input[0].distance, 4);
+        assert_eq!(input[3].direction, Direction::Down);
+        assert_eq!(input[3].distance, 1);
+    }
+
+    #[test]
+    fn test_load_invalid_input() {
+        let err = load_input("R 4\nU 4\nX 3\n").err().unwrap();
+        assert_eq!(err, ParseError::new(3, 1, "invalid direction 'X'"));
+
+        let err = load_input("R 4\n\nU four\n").err().unwrap();
+        assert_eq!(err, ParseError::new(3, 3, "invalid distance 'four'"));
+    }
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(Day09.part1(&Day09.parse(EX1).unwrap()), 13);
+    }
+
+    #[test]
+    fn test_solve2a() {
+        assert_eq!(Day09.part2(&Day09.parse(EX1).unwrap()), 1);
+    }
+
+    #[test]
+    fn test_solve2b() {
+        assert_eq!(Day09.part2(&Day09.parse(EX2).unwrap()), 36);
+    }
+
+    #[test]
+    fn move_to_check_move() {
+        assert!(!follow(&mut Position::new(0, 0), &Position::new(1, 0)));
+        assert!(!follow(&mut Position::new(0, 0), &Position::new(0, 1)));
+        assert!(!follow(&mut Position::new(0, 0), &Position::new(-1, 0)));
+        assert!(!follow(&mut Position::new(0, 0), &Position::new(0, -1)));
+
+        assert!(follow(&mut Position::new(0, 0), &Position::new(2, 0)));
+        assert!(follow(&mut Position::new(0, 0), &Position::new(0, 2)));
+        assert!(follow(&mut Position::new(0, 0), &Position::new(-2, 1)));
+        assert!(follow(&mut Position::new(0, 0), &Position::new(1, -2)));
+    }
+
+    #[test]
+    fn move_to_same_row() {
+        let mut p1 = Position::new(0, 0);
+        let p2 = Position::new(2, 0);
+        follow(&mut p1, &p2);
+        assert_eq!(p1, Position::new(1, 0));
+
+        let mut p1 = Position::new(-3, 3);
+        let p2 = Position::new(-5, 3);
+        follow(&mut p1, &p2);
+        assert_eq!(p1, Position::new(-4, 3));
+    }
+
+    #[test]
+    fn move_to_same_column() {
+        let mut p1 = Position::new(0, 0);
+        let p2 = Position::new(0, 2);
+        follow(&mut p1, &p2);
+        assert_eq!(p1, Position::new(0, 1));
+
+        let mut p1 = Position::new(1, -3);
+        let p2 = Position::new(1, -5);
+        follow(&mut p1, &p2);
+        assert_eq!(p1, Position::new(1, -4));
+    }
+
+    #[test]
+    fn move_to_diagonally() {
+        let mut p1 = Position::new(0, 0);
+        let p2 = Position::new(2, 1);
+        follow(&mut p1, &p2);
+        assert_eq!(p1, Position::new(1, 1));
+
+        let mut p1 = Position::new(0, 0);
+        let p2 = Position::new(2, 2);
+        follow(&mut p1, &p2);
+        assert_eq!(p1, Position::new(1, 1));
+    }
+
+    #[test]
+    fn test_move_in() {
+        let mut p = Position::new(0, 0);
+        p = p.step(Direction::Left);
+        assert_eq!(p, Position::new(-1, 0));
+        p = p.step(Direction::Up);
+        assert_eq!(p, Position::new(-1, -1));
+        p = p.step(Direction::Right);
+        assert_eq!(p, Position::new(0, -1));
+        p = p.step(Direction::Down);
+        assert_eq!(p, Position::new(0, 0));
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Moves the rope like `RopeSimulator::step`, but updates every knot instead of stopping
+    /// at the first one that stays in place.
+    fn step_all_knots(knots: &mut [Position], direction: Direction) {
+        knots[0] += direction.offset();
+        for idx in 1..knots.len() {
+            let other = knots[idx - 1];
+            follow(&mut knots[idx], &other);
+        }
+    }
+
+    fn motions_strategy() -> impl Strategy<Value = Motions> {
+        let motion = (prop::sample::select(Direction::ALL.to_vec()), 1..10usize).prop_map(
+            |(direction, distance)| Motion {
+                direction,
+                distance,
+            },
+        );
+        prop::collection::vec(motion, 0..50)
+    }
+
+    proptest! {
+        #[test]
+        fn step_matches_full_knot_update(motions in motions_strategy(), knot_count in 2..12usize) {
+            let mut simulator = RopeSimulator::new(knot_count);
+            let mut knots = vec![Position::ORIGIN; knot_count];
+            let mut tail_positions = HashSet::from([Position::ORIGIN]);
+            for motion in &motions {
+                for _ in 0..motion.distance {
+                    simulator.step(motion.direction);
+                    step_all_knots(&mut knots, motion.direction);
+                    tail_positions.insert(knots[knot_count - 1]);
+                    prop_assert_eq!(&simulator.knots, &knots);
+                }
+            }
+            prop_assert_eq!(simulator.tail_positions, tail_positions);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// The number of cycles the CRT needs to draw a whole screen.
+const SCREEN_CYCLES: usize = 240;
+
+/// Generates a program of `size` random instructions, padded with `noop` so that it runs for
+/// at least the 240 cycles needed to draw the screen.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut program = String::new();
+    let mut cycles = 0;
+    for _ in 0..size {
+        if rng.gen_bool(0.6) {
+            program.push_str(&format!("addx {}\n", rng.gen_range(-20..=20)));
+            cycles += 2;
+        } else {
+            program.push_str("noop\n");
+            cycles += 1;
+        }
+    }
+    for _ in cycles..SCREEN_CYCLES {
+        program.push_str("noop\n");
+    }
+    program
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::Day10;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(10, 150), generate(10, 150));
+        assert_ne!(generate(10, 150), generate(11, 150));
+    }
+
+    #[test]
+    fn generated_programs_can_be_solved() {
+        for seed in 0..10 {
+            let program = Day10.parse(&generate(seed, seed as usize * 40)).unwrap();
+            assert!(Day10.part1(&program).is_ok());
+            assert_eq!(Day10.part2(&program).unwrap().lines().count(), 6);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use std::{ops::Index, slice::Iter, str::FromStr};
+
+use aoc_common::{parse_lines, ParseError, Solution};
+
+pub mod generate;
+
+type Register = isize;
+
+#[derive(Debug, PartialEq)]
+pub enum Instruction {
+    Noop,
+    AddX(Register),
+}
+
+impl FromStr for Instruction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        let parts: Vec<&str> = s.split(' ').collect();
+        match parts.as_slice() {
+            ["addx", arg] => arg
+                .parse()
+                .map(Instruction::AddX)
+                .map_err(|_| ParseError::at_column(6, format!("invalid argument '{arg}'"))),
+            ["noop"] => Ok(Instruction::Noop),
+            _ => Err(ParseError::at_column(
+                1,
+                format!("invalid instruction '{s}'"),
+            )),
+        }
+    }
+}
+
+pub struct Program(Vec<Instruction>);
+
+impl Program {
+    fn iter(&self) -> Iter<'_, Instruction> {
+        self.0.iter()
+    }
+}
+
+impl Index<usize> for Program {
+    type Output = Instruction;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl FromStr for Program {
+    type Err = ParseError;
+
+    fn from_str(src: &str) -> Result<Self, ParseError> {
+        parse_lines(src, str::parse).map(Program)
+    }
+}
+
+struct Cpu;
+
+impl Cpu {
+    fn new() -> Cpu {
+        Cpu {}
+    }
+
+    fn run_program(&self, program: &Program) -> Vec<Register> {
+        program
+            .iter()
+            .fold((vec![1, 1], 1), |(mut res, mut x), instruction| {
+                match instruction {
+                    Instruction::Noop => res.push(x),
+
+                    Instruction::AddX(arg) => {
+                        res.push(x);
+                        x += arg;
+                        res.push(x);
+                    }
+                }
+                (res, x)
+            })
+            .0
+    }
+}
+
+fn run_program(program: &Program) -> Vec<Register> {
+    let cpu = Cpu::new();
+    cpu.run_program(program)
+}
+
+/// Runs `program`, returning the register during each cycle up to `cycles`, or an error
+/// after the last instruction if the program ends sooner.
+fn run_for(program: &Program, cycles: usize) -> Result<Vec<Register>, ParseError> {
+    let regs = run_program(program);
+    // the register is listed before the first cycle and after the last one
+    let ran = regs.len() - 2;
+    if ran < cycles {
+        return Err(ParseError::new(
+            program.0.len() + 1,
+            1,
+            format!("the program ends after {ran} cycles, but {cycles} are needed"),
+        ));
+    }
+    Ok(regs)
+}
+
+/// Sums the signal strengths during cycles 20, 60, ..., 220.
+fn signal_strength(program: &Program) -> Result<isize, ParseError> {
+    let regs = run_for(program, 220)?;
+    Ok((20..=220)
+        .step_by(40)
+        .map(|idx| regs[idx] * idx as isize)
+        .sum())
+}
+
+/// Draws the screen, one pixel per cycle.
+fn render_crt(program: &Program) -> Result<String, ParseError> {
+    let regs = run_for(program, 240)?;
+    let mut screen = String::new();
+    for row in 0..=5 {
+        for col in 0..=39 {
+            let idx = 1 + (row * 40 + col);
+            if (regs[idx] - col as isize).abs() <= 1 {
+                screen.push('#');
+            } else {
+                screen.push('.');
+            }
+        }
+        screen.push('\n');
+    }
+    Ok(screen)
+}
+
+pub static INPUT: &str = include_str!("input.txt");
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Program;
+    type Answer1 = Result<isize, ParseError>;
+    type Answer2 = Result<String, ParseError>;
+
+    fn parse(&self, input: &str) -> Result<Program, ParseError> {
+        input.parse()
+    }
+
+    fn part1(&self, program: &Program) -> Result<isize, ParseError> {
+        signal_strength(program)
+    }
+
+    fn part2(&self, program: &Program) -> Result<String, ParseError> {
+        render_crt(program)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EX1: &str = include_str!("example1.txt");
+
+    #[test]
+    fn load_program_from_string() {
+        let program: Program = EX1.parse().unwrap();
+        assert_eq!(program[23], Instruction::AddX(1));
+        assert_eq!(program[145], Instruction::Noop);
+    }
+
+    #[test]
+    fn load_invalid_program() {
+        let err = "noop\naddx 3\naddx x\n".parse::<Program>().err().unwrap();
+        assert_eq!(err, ParseError::new(3, 6, "invalid argument 'x'"));
+
+        let err = "noop\nmul 3\n".parse::<Program>().err().unwrap();
+        assert_eq!(err, ParseError::new(2, 1, "invalid instruction 'mul 3'"));
+    }
+
+    #[test]
+    fn short_programs() {
+        let program = Day10.parse("noop\naddx 5\n").unwrap();
+        let err = ParseError::new(3, 1, "the program ends after 3 cycles, but 220 are needed");
+        assert_eq!(Day10.part1(&program), Err(err));
+        let err = ParseError::new(3, 1, "the program ends after 3 cycles, but 240 are needed");
+        assert_eq!(Day10.part2(&program), Err(err));
+
+        let program = Day10.parse(&"noop\n".repeat(220)).unwrap();
+        assert_eq!(Day10.part1(&program), Ok(20 + 60 + 100 + 140 + 180 + 220));
+        assert!(Day10.part2(&program).is_err());
+        let err = ParseError::new(1, 1, "the program ends after 0 cycles, but 220 are needed");
+        assert_eq!(Day10.part1(&Day10.parse("").unwrap()), Err(err));
+    }
+
+    #[test]
+    fn test_example1() {
+        let program: Program = EX1.parse().unwrap();
+        let res = run_program(&program);
+        assert_eq!(res.len(), 242);
+        assert_eq!(res[20], 21, "20");
+        assert_eq!(res[60], 19, "60");
+        assert_eq!(res[100], 18, "100");
+        assert_eq!(res[140], 21, "140");
+        assert_eq!(res[180], 16, "180");
+        assert_eq!(res[220], 18, "220");
+    }
+
+    #[test]
+    fn test_solve1() {
+        assert_eq!(Day10.part1(&Day10.parse(EX1).unwrap()), Ok(13140));
+    }
+
+    #[test]
+    fn test_solve2() {
+        assert_eq!(
+            Day10.part2(&Day10.parse(EX1).unwrap()).unwrap(),
+            "##..##..##..##..##..##..##..##..##..##..\n\
+             ###...###...###...###...###...###...###.\n\
+             ####....####....####....####....####....\n\
+             #####.....#####.....#####.....#####.....\n\
+             ######......######......######......####\n\
+             #######.......#######.......#######.....\n"
+        );
+    }
+}
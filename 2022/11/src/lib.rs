@@ -0,0 +1,453 @@
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::str::FromStr;
+
+use aoc_common::{ParseError, Solution};
+
+pub static INPUT: &str = include_str!("input.txt");
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Operation {
+    Times(Item),
+    Add(Item),
+    Square,
+}
+
+#[derive(Clone, Copy)]
+enum WorryManagement {
+    DivideByThree,
+    Modulo(Item),
+}
+
+type Item = u128;
+type Items = VecDeque<Item>;
+type Monkeys = Vec<RefCell<Monkey>>;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Monkey {
+    items: Items,
+    operation: Operation,
+    divisor: Item,
+    true_monkey: usize,
+    false_monkey: usize,
+    inspection_count: usize,
+}
+
+impl Monkey {
+    fn play_turn(&mut self, others: &Monkeys, worry_management: WorryManagement) {
+        while let Some(old) = self.items.pop_front() {
+            self.inspection_count += 1;
+            let new = match self.operation {
+                Operation::Times(num) => old.wrapping_mul(num),
+                Operation::Add(num) => old.wrapping_add(num),
+                Operation::Square => old.wrapping_mul(old),
+            };
+
+            let new = match worry_management {
+                WorryManagement::DivideByThree => new / 3,
+                WorryManagement::Modulo(num) => new % num,
+            };
+
+            let receiver = if new % self.divisor == 0 {
+                self.true_monkey
+            } else {
+                self.false_monkey
+            };
+
+            others[receiver].borrow_mut().catches(new);
+        }
+    }
+
+    fn catches(&mut self, item: Item) {
+        self.items.push_back(item);
+    }
+}
+
+/// A line of the input together with its 1-based line number.
+type Line<'a> = (usize, &'a str);
+
+fn parse_number<T: FromStr>((line, _): Line, column: usize, s: &str) -> Result<T, ParseError> {
+    s.parse()
+        .map_err(|_| ParseError::new(line, column, format!("invalid number '{s}'")))
+}
+
+/// Returns the text following `prefix` in the `idx`th line of the block and its column.
+fn field<'a>(block: &[Line<'a>], idx: usize, prefix: &str) -> Result<(usize, &'a str), ParseError> {
+    let &(line, text) = block.get(idx).ok_or_else(|| {
+        let (last_line, _) = block[block.len() - 1];
+        ParseError::new(last_line + 1, 1, format!("expected '{prefix}'"))
+    })?;
+    let trimmed = text.trim_start();
+    let indent = text.len() - trimmed.len();
+    trimmed
+        .strip_prefix(prefix)
+        .map(|value| (indent + prefix.len() + 1, value.trim_end()))
+        .ok_or_else(|| ParseError::new(line, indent + 1, format!("expected '{prefix}'")))
+}
+
+fn parse_operation(line: Line, column: usize, operation: &str) -> Result<Operation, ParseError> {
+    match operation.split(' ').collect::<Vec<_>>().as_slice() {
+        ["old", "*", "old"] => Ok(Operation::Square),
+        ["old", "*", num] => parse_number(line, column + 6, num).map(Operation::Times),
+        ["old", "+", num] => parse_number(line, column + 6, num).map(Operation::Add),
+        _ => Err(ParseError::new(
+            line.0,
+            column,
+            format!("invalid operation '{operation}'"),
+        )),
+    }
+}
+
+fn parse_monkey(block: &[Line]) -> Result<Monkey, ParseError> {
+    field(block, 0, "Monkey ")?;
+
+    let (column, items) = field(block, 1, "Starting items: ")?;
+    let mut offset = 0;
+    let items = items
+        .split(", ")
+        .map(|item| {
+            let item_column = column + offset;
+            offset += item.len() + 2;
+            parse_number(block[1], item_column, item)
+        })
+        .collect::<Result<_, _>>()?;
+
+    let (column, operation) = field(block, 2, "Operation: new = ")?;
+    let operation = parse_operation(block[2], column, operation)?;
+
+    let (column, divisor) = field(block, 3, "Test: divisible by ")?;
+    let divisor = parse_number(block[3], column, divisor)?;
+    if divisor == 0 {
+        return Err(ParseError::new(
+            block[3].0,
+            column,
+            "cannot test for divisibility by 0",
+        ));
+    }
+
+    let (column, true_monkey) = field(block, 4, "If true: throw to monkey ")?;
+    let true_monkey = parse_number(block[4], column, true_monkey)?;
+
+    let (column, false_monkey) = field(block, 5, "If false: throw to monkey ")?;
+    let false_monkey = parse_number(block[5], column, false_monkey)?;
+
+    Ok(Monkey {
+        items,
+        operation,
+        divisor,
+        true_monkey,
+        false_monkey,
+        inspection_count: 0,
+    })
+}
+
+fn load_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let lines: Vec<Line> = input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| (idx + 1, line))
+        .collect();
+    let blocks: Vec<&[Line]> = lines
+        .split(|(_, line)| line.trim().is_empty())
+        .filter(|block| !block.is_empty())
+        .collect();
+
+    let monkeys = blocks
+        .iter()
+        .map(|block| parse_monkey(block))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // the monkey business is the product of the two highest inspection counts
+    if monkeys.len() < 2 {
+        let line = lines.last().map_or(1, |&(line, _)| line + 1);
+        return Err(ParseError::new(line, 1, "expected at least two monkeys"));
+    }
+
+    for (idx, (monkey, block)) in monkeys.iter().zip(&blocks).enumerate() {
+        for (line_idx, target) in [(4, monkey.true_monkey), (5, monkey.false_monkey)] {
+            let (line, text) = block[line_idx];
+            let column = text.trim_end().len() - target.to_string().len() + 1;
+            if target >= monkeys.len() {
+                return Err(ParseError::new(
+                    line,
+                    column,
+                    format!("there is no monkey {target}"),
+                ));
+            }
+            if target == idx {
+                return Err(ParseError::new(
+                    line,
+                    column,
+                    "a monkey cannot throw to itself",
+                ));
+            }
+        }
+    }
+
+    Ok(monkeys)
+}
+
+fn play_round(monkeys: &Monkeys, worry_management: WorryManagement) {
+    for m in monkeys {
+        m.borrow_mut().play_turn(monkeys, worry_management);
+    }
+}
+
+fn play(monkeys: &Monkeys, num_rounds: usize, worry_management: WorryManagement) -> usize {
+    for _ in 1..=num_rounds {
+        play_round(monkeys, worry_management);
+    }
+
+    let mut counts: Vec<usize> = monkeys
+        .iter()
+        .map(|e| e.borrow().inspection_count)
+        .collect();
+    counts.sort();
+
+    counts[counts.len() - 1] * counts[counts.len() - 2]
+}
+
+fn into_monkeys(monkeys: &[Monkey]) -> Monkeys {
+    monkeys.iter().cloned().map(RefCell::new).collect()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<Monkey>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<Monkey>, ParseError> {
+        load_monkeys(input)
+    }
+
+    fn part1(&self, monkeys: &Vec<Monkey>) -> usize {
+        play(&into_monkeys(monkeys), 20, WorryManagement::DivideByThree)
+    }
+
+    fn part2(&self, monkeys: &Vec<Monkey>) -> usize {
+        let modulo = monkeys.iter().fold(1, |m, e| m * e.divisor);
+        play(
+            &into_monkeys(monkeys),
+            10000,
+            WorryManagement::Modulo(modulo),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_monkeys() -> Monkeys {
+        vec![
+            RefCell::new(Monkey {
+                items: [79, 98].into(),
+                operation: Operation::Times(19),
+                divisor: 23,
+                true_monkey: 2,
+                false_monkey: 3,
+                inspection_count: 0,
+            }),
+            RefCell::new(Monkey {
+                items: [54, 65, 75, 74].into(),
+                operation: Operation::Add(6),
+                divisor: 19,
+                true_monkey: 2,
+                false_monkey: 0,
+                inspection_count: 0,
+            }),
+            RefCell::new(Monkey {
+                items: [79, 60, 97].into(),
+                operation: Operation::Square,
+                divisor: 13,
+                true_monkey: 1,
+                false_monkey: 3,
+                inspection_count: 0,
+            }),
+            RefCell::new(Monkey {
+                items: [74].into(),
+                operation: Operation::Add(3),
+                divisor: 17,
+                true_monkey: 0,
+                false_monkey: 1,
+                inspection_count: 0,
+            }),
+        ]
+    }
+
+    static EX: &str = include_str!("example.txt");
+
+    #[test]
+    fn test_load_monkeys() {
+        let monkeys = load_monkeys(EX).unwrap();
+        let expected = example_monkeys();
+        assert_eq!(monkeys.len(), expected.len());
+        for (monkey, expected) in monkeys.iter().zip(expected) {
+            assert_eq!(*monkey, expected.into_inner());
+        }
+    }
+
+    #[test]
+    fn test_load_invalid_monkeys() {
+        let input = "Monkey 0:\n  Starting items: 79, x8\n";
+        let err = load_monkeys(input).err().unwrap();
+        assert_eq!(err, ParseError::new(2, 23, "invalid number 'x8'"));
+
+        let input = "Monkey 0:\n  Starting items: 79\n  Operation: new = old - 3\n";
+        let err = load_monkeys(input).err().unwrap();
+        assert_eq!(err, ParseError::new(3, 20, "invalid operation 'old - 3'"));
+
+        let input = "Monkey 0:\n  Starting items: 79\n  Operation: new = old * 3\n";
+        let err = load_monkeys(input).err().unwrap();
+        assert_eq!(err, ParseError::new(4, 1, "expected 'Test: divisible by '"));
+
+        let input = EX.replace("throw to monkey 3", "throw to monkey 7");
+        let err = load_monkeys(&input).err().unwrap();
+        assert_eq!(err, ParseError::new(6, 31, "there is no monkey 7"));
+
+        let input = EX.replacen("throw to monkey 2", "throw to monkey 0", 1);
+        let err = load_monkeys(&input).err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(5, 30, "a monkey cannot throw to itself")
+        );
+
+        let input = EX.replace("divisible by 19", "divisible by 0");
+        let err = load_monkeys(&input).err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(11, 22, "cannot test for divisibility by 0")
+        );
+
+        let err = load_monkeys("").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 1, "expected at least two monkeys"));
+        let one_monkey: Vec<&str> = EX.lines().take(6).collect();
+        let err = load_monkeys(&one_monkey.join("\n")).err().unwrap();
+        assert_eq!(err, ParseError::new(7, 1, "expected at least two monkeys"));
+    }
+
+    #[test]
+    fn test_example1() {
+        let monkeys = example_monkeys();
+        let level = play(&monkeys, 20, WorryManagement::DivideByThree);
+        assert_eq!(level, 105 * 101);
+    }
+
+    #[test]
+    fn test_example2() {
+        let monkeys = example_monkeys();
+        let modulo = monkeys.iter().fold(1, |m, e| m * e.borrow().divisor);
+        let level = play(&monkeys, 10000, WorryManagement::Modulo(modulo));
+        assert_eq!(level, 52013 * 52166);
+    }
+}
+
+#[cfg(test)]
+mod properties {
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// A monkey keeping track of its exact worry levels.
+    struct ExactMonkey {
+        items: VecDeque<BigUint>,
+        inspection_count: usize,
+    }
+
+    /// Plays a round without any worry management, so the levels grow without bounds.
+    fn play_exact_round(monkeys: &[Monkey], exact: &mut [ExactMonkey]) {
+        for (idx, monkey) in monkeys.iter().enumerate() {
+            while let Some(old) = exact[idx].items.pop_front() {
+                exact[idx].inspection_count += 1;
+                let new = match monkey.operation {
+                    Operation::Times(num) => old * num,
+                    Operation::Add(num) => old + num,
+                    Operation::Square => &old * &old,
+                };
+                let receiver = if &new % monkey.divisor == BigUint::from(0u8) {
+                    monkey.true_monkey
+                } else {
+                    monkey.false_monkey
+                };
+                exact[receiver].items.push_back(new);
+            }
+        }
+    }
+
+    fn monkeys_strategy() -> impl Strategy<Value = Vec<Monkey>> {
+        let operation = prop_oneof![
+            (2..20 as Item).prop_map(Operation::Times),
+            (1..10 as Item).prop_map(Operation::Add),
+        ];
+        (2..6usize)
+            .prop_flat_map(move |count| {
+                let monkey = (
+                    prop::collection::vec_deque(1..100 as Item, 0..5),
+                    operation.clone(),
+                    2..24 as Item,
+                    0..count - 1,
+                    0..count - 1,
+                );
+                (prop::collection::vec(monkey, count), any::<bool>())
+            })
+            .prop_map(|(monkeys, square)| {
+                monkeys
+                    .into_iter()
+                    .enumerate()
+                    .map(
+                        |(idx, (items, operation, divisor, true_monkey, false_monkey))| {
+                            // monkeys never throw to themselves, and squaring is restricted to the
+                            // first monkey to keep the exact levels reasonably small
+                            let skip_self =
+                                |target| if target >= idx { target + 1 } else { target };
+                            Monkey {
+                                items,
+                                operation: if idx == 0 && square {
+                                    Operation::Square
+                                } else {
+                                    operation
+                                },
+                                divisor,
+                                true_monkey: skip_self(true_monkey),
+                                false_monkey: skip_self(false_monkey),
+                                inspection_count: 0,
+                            }
+                        },
+                    )
+                    .collect()
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn modulo_matches_exact_worry_levels(monkeys in monkeys_strategy(), rounds in 1..10usize) {
+            let modulo = monkeys.iter().fold(1, |m, e| m * e.divisor);
+            let reduced = into_monkeys(&monkeys);
+            let mut exact: Vec<ExactMonkey> = monkeys
+                .iter()
+                .map(|monkey| ExactMonkey {
+                    items: monkey.items.iter().map(|&item| BigUint::from(item)).collect(),
+                    inspection_count: 0,
+                })
+                .collect();
+
+            for _ in 0..rounds {
+                play_round(&reduced, WorryManagement::Modulo(modulo));
+                play_exact_round(&monkeys, &mut exact);
+                for (monkey, exact) in reduced.iter().zip(&exact) {
+                    let monkey = monkey.borrow();
+                    prop_assert_eq!(monkey.inspection_count, exact.inspection_count);
+                    let expected: Items = exact
+                        .items
+                        .iter()
+                        .map(|item| Item::try_from(item % modulo).unwrap())
+                        .collect();
+                    prop_assert_eq!(&monkey.items, &expected);
+                }
+            }
+        }
+    }
+}
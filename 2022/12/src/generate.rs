@@ -0,0 +1,72 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Climbing from `a` to `z` takes at least this many steps.
+const MIN_PATH_STEPS: usize = 25;
+
+/// Generates a heightmap `size` columns wide (but at least wide enough to climb from `a` to
+/// `z`) with `S` in the first and `E` in the last column.
+///
+/// A path that never climbs more than one level per step is laid out from `S` to `E`, so the
+/// map always has a solution; the remaining cells are random.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let width = size.max(MIN_PATH_STEPS + 1);
+    let height = (size / 2).max(2);
+    let mut rows: Vec<Vec<u8>> = (0..height)
+        .map(|_| (0..width).map(|_| rng.gen_range(b'a'..=b'z')).collect())
+        .collect();
+
+    // a path of right and vertical steps from the start to the end row
+    let start_row = rng.gen_range(0..height);
+    let end_row = rng.gen_range(0..height);
+    let mut path = vec![(start_row, 0)];
+    let (mut row, mut col) = (start_row, 0);
+    while (row, col) != (end_row, width - 1) {
+        let vertical = row != end_row && (col == width - 1 || rng.gen_bool(0.3));
+        if vertical && row < end_row {
+            row += 1;
+        } else if vertical {
+            row -= 1;
+        } else {
+            col += 1;
+        }
+        path.push((row, col));
+    }
+
+    // climbs at most one level per step as the path has at least 25 steps
+    let steps = path.len() - 1;
+    for (idx, &(row, col)) in path.iter().enumerate() {
+        rows[row][col] = b'a' + (idx * MIN_PATH_STEPS / steps) as u8;
+    }
+    rows[start_row][0] = b'S';
+    rows[end_row][width - 1] = b'E';
+
+    rows.into_iter()
+        .map(|row| String::from_utf8(row).unwrap() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::Day12;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(12, 30), generate(12, 30));
+        assert_ne!(generate(12, 30), generate(13, 30));
+    }
+
+    #[test]
+    fn generated_maps_can_be_solved() {
+        for seed in 0..10 {
+            let map = Day12.parse(&generate(seed, seed as usize * 6)).unwrap();
+            let steps = Day12.part1(&map);
+            assert!(steps >= MIN_PATH_STEPS);
+            assert!(Day12.part2(&map) <= steps);
+        }
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashSet;
+use std::mem;
+
+use aoc_common::{ParseError, Solution};
+use aoc_geometry::{Direction, Point};
+use aoc_grid::Grid;
+
+pub mod generate;
+
+/// A position on the map, with `x` being the column and `y` the row.
+type Position = Point<usize>;
+
+pub struct Map {
+    heights: Grid<u8>,
+    start_pos: Position,
+    end_pos: Position,
+}
+
+impl Map {
+    fn load(input: &str) -> Result<Map, ParseError> {
+        let mut start_pos: Option<Position> = None;
+        let mut end_pos: Option<Position> = None;
+        let heights = Grid::parse(input, |(row, col), c| match c {
+            'a'..='z' => Some(c as u8),
+            'S' => {
+                start_pos = Some(Position::new(col, row));
+                Some(b'a')
+            }
+            'E' => {
+                end_pos = Some(Position::new(col, row));
+                Some(b'z')
+            }
+            _ => None,
+        })?;
+
+        let missing = |what| ParseError::at_offset(input, input.len(), format!("missing {what}"));
+        Ok(Map {
+            heights,
+            start_pos: start_pos.ok_or_else(|| missing("start position 'S'"))?,
+            end_pos: end_pos.ok_or_else(|| missing("end position 'E'"))?,
+        })
+    }
+
+    fn projected_position(&self, from: Position, direction: Direction) -> Option<Position> {
+        from.checked_add_signed(direction.offset())
+            .filter(|to| self.heights.get((to.y, to.x)).is_some())
+    }
+
+    fn is_direction_valid(&self, start: Position, direction: Direction) -> bool {
+        let start_height = self.height_at(start);
+        if let Some(to) = self.projected_position(start, direction) {
+            let dest_height = self.height_at(to);
+            start_height >= dest_height - 1
+        } else {
+            false
+        }
+    }
+
+    fn height_at(&self, pos: Position) -> u8 {
+        self.heights[(pos.y, pos.x)]
+    }
+
+    #[cfg(test)]
+    fn num_cols(&self) -> usize {
+        self.heights.width()
+    }
+
+    #[cfg(test)]
+    fn num_rows(&self) -> usize {
+        self.heights.height()
+    }
+
+    fn valid_directions(&self, pos: Position) -> HashSet<Direction> {
+        Direction::ALL
+            .into_iter()
+            .filter(|dir| self.is_direction_valid(pos, *dir))
+            .collect()
+    }
+
+    fn valid_positions(&self, pos: Position, visited: &mut HashSet<Position>) -> HashSet<Position> {
+        let result = self
+            .valid_directions(pos)
+            .into_iter()
+            .map(|dir| self.projected_position(pos, dir))
+            // we only use valid directions so unwrap won't fail
+            .map(|pos| pos.unwrap())
+            .collect::<HashSet<_>>()
+            .difference(visited)
+            .copied()
+            .collect();
+        visited.extend(&result);
+        result
+    }
+
+    fn find_points(&self, elevation: u8) -> Vec<Position> {
+        self.heights
+            .iter()
+            .filter(|(_, height)| **height == elevation)
+            .map(|((row, col), _)| Position::new(col, row))
+            .collect()
+    }
+}
+
+fn find_path(map: &Map, start: Position) -> Option<usize> {
+    let mut visited: HashSet<Position> = HashSet::new();
+    let mut current_set: HashSet<Position> = [start].into();
+
+    let mut count = 0;
+
+    loop {
+        for pos in mem::take(&mut current_set) {
+            if pos == map.end_pos {
+                return Some(count);
+            }
+            current_set.extend(map.valid_positions(pos, &mut visited));
+        }
+
+        if current_set.is_empty() {
+            return None;
+        }
+
+        count += 1;
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Map;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Map, ParseError> {
+        Map::load(input)
+    }
+
+    fn part1(&self, map: &Map) -> usize {
+        find_path(map, map.start_pos).unwrap()
+    }
+
+    fn part2(&self, map: &Map) -> usize {
+        // find all points with elevation 'a'
+        let mut lens = map
+            .find_points(b'a')
+            .iter()
+            .filter_map(|point| find_path(map, *point))
+            .collect::<Vec<_>>();
+
+        lens.sort();
+        *lens.first().unwrap()
+    }
+}
+
+pub static INPUT: &str = include_str!("input.txt");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EX1: &str = include_str!("example.txt");
+
+    #[test]
+    fn test_load_map() {
+        let map = Map::load(EX1).unwrap();
+        assert_eq!(map.num_cols(), 8);
+        assert_eq!(map.num_rows(), 5);
+        assert_eq!(map.height_at(Position::new(0, 0)), b'a');
+        assert_eq!(map.height_at(Position::new(7, 4)), b'i');
+        assert_eq!(map.start_pos, Position::new(0, 0));
+        assert_eq!(map.end_pos, Position::new(5, 2));
+    }
+
+    #[test]
+    fn test_load_invalid_map() {
+        let err = Map::load("Sabc\nabEd\nab-d\n").err().unwrap();
+        assert_eq!(err, ParseError::new(3, 3, "invalid character '-'"));
+
+        let err = Map::load("abc\nabE\n").err().unwrap();
+        assert_eq!(err, ParseError::new(3, 1, "missing start position 'S'"));
+
+        let err = Map::load("Sbc\nabc").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 4, "missing end position 'E'"));
+    }
+
+    #[test]
+    fn test_projected_position() {
+        let map = Map::load(EX1).unwrap();
+        assert_eq!(
+            map.projected_position(Position::new(0, 0), Direction::Right),
+            Some(Position::new(1, 0))
+        );
+        assert_eq!(
+            map.projected_position(Position::new(0, 0), Direction::Left),
+            None
+        );
+        assert_eq!(
+            map.projected_position(Position::new(7, 0), Direction::Right),
+            None
+        );
+        assert_eq!(
+            map.projected_position(Position::new(7, 5), Direction::Down),
+            None
+        );
+        assert_eq!(
+            map.projected_position(Position::new(7, 5), Direction::Up),
+            Some(Position::new(7, 4))
+        );
+    }
+
+    #[test]
+    fn test_possible_directions() {
+        let map = Map::load(EX1).unwrap();
+
+        assert_eq!(
+            map.valid_directions(Position::new(0, 0)),
+            [Direction::Down, Direction::Right].into()
+        );
+
+        assert_eq!(
+            map.valid_directions(Position::new(3, 2)),
+            [Direction::Left, Direction::Down, Direction::Up].into()
+        );
+    }
+
+    #[test]
+    fn example1() {
+        let sol = Day12.part1(&Map::load(EX1).unwrap());
+        assert_eq!(sol, 31);
+    }
+
+    #[test]
+    fn example2() {
+        let sol = Day12.part2(&Map::load(EX1).unwrap());
+        assert_eq!(sol, 29);
+    }
+}
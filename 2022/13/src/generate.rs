@@ -0,0 +1,60 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+const MAX_DEPTH: usize = 4;
+
+fn write_list(rng: &mut impl Rng, depth: usize, out: &mut String) {
+    out.push('[');
+    for idx in 0..rng.gen_range(0..=5) {
+        if idx > 0 {
+            out.push(',');
+        }
+        if depth < MAX_DEPTH && rng.gen_bool(0.3) {
+            write_list(rng, depth + 1, out);
+        } else {
+            out.push_str(&rng.gen_range(0..=10).to_string());
+        }
+    }
+    out.push(']');
+}
+
+/// Generates `size` pairs of randomly nested packets.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let mut packets = String::new();
+    for idx in 0..size {
+        if idx > 0 {
+            packets.push('\n');
+        }
+        for _ in 0..2 {
+            write_list(&mut rng, 1, &mut packets);
+            packets.push('\n');
+        }
+    }
+    packets
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::Day13;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(13, 20), generate(13, 20));
+        assert_ne!(generate(13, 20), generate(14, 20));
+    }
+
+    #[test]
+    fn generated_packets_can_be_solved() {
+        for seed in 0..10 {
+            let size = seed as usize * 10;
+            let packets = Day13.parse(&generate(seed, size)).unwrap();
+            assert_eq!(packets.len(), 2 * size);
+            assert!(Day13.part1(&packets) <= size * (size + 1) / 2);
+            Day13.part2(&packets);
+        }
+    }
+}
@@ -0,0 +1,191 @@
+use aoc_common::{ParseError, Solution};
+use itertools::Itertools;
+use lalrpop_util::lalrpop_mod;
+
+use crate::ast::PacketData;
+use crate::ast::PacketData::{Integer, List};
+
+lalrpop_mod!(#[allow(clippy::all)] pub parser);
+pub mod ast;
+pub mod generate;
+
+fn packet_pairs(packets: &[PacketData]) -> Vec<(&PacketData, &PacketData)> {
+    packets.iter().tuples::<(_, _)>().collect()
+}
+
+type LalrpopError<'input> =
+    lalrpop_util::ParseError<usize, parser::Token<'input>, (usize, &'static str)>;
+
+fn expectation(expected: &[String]) -> String {
+    if expected.is_empty() {
+        String::new()
+    } else {
+        format!(", expected {}", expected.join(" or "))
+    }
+}
+
+fn to_parse_error(line: &str, err: LalrpopError) -> ParseError {
+    use lalrpop_util::ParseError::*;
+
+    let (location, message) = match err {
+        InvalidToken { location } => (location, "invalid token".to_string()),
+        UnrecognizedEOF { location, expected } => (
+            location,
+            format!("unexpected end of line{}", expectation(&expected)),
+        ),
+        UnrecognizedToken {
+            token: (location, token, _),
+            expected,
+        } => (
+            location,
+            format!("unexpected '{token}'{}", expectation(&expected)),
+        ),
+        ExtraToken {
+            token: (location, token, _),
+        } => (location, format!("unexpected '{token}'")),
+        User {
+            error: (location, message),
+        } => (location, message.to_string()),
+    };
+    ParseError::at_offset(line, location, message)
+}
+
+fn load_packets(input: &str) -> Result<Vec<PacketData>, ParseError> {
+    let parser = parser::PacketDataParser::new();
+    aoc_common::parse_lines(input, |l| {
+        parser.parse(l).map_err(|err| to_parse_error(l, err))
+    })
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<PacketData>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Vec<PacketData>, ParseError> {
+        load_packets(input)
+    }
+
+    fn part1(&self, packets: &Vec<PacketData>) -> usize {
+        packet_pairs(packets)
+            .into_iter()
+            .enumerate()
+            .fold(
+                0,
+                |res, (idx, (lhs, rhs))| {
+                    if lhs < rhs {
+                        res + (idx + 1)
+                    } else {
+                        res
+                    }
+                },
+            )
+    }
+
+    fn part2(&self, packets: &Vec<PacketData>) -> usize {
+        let divider1 = List(vec![List(vec![Integer(2)])]);
+        let divider2 = List(vec![List(vec![Integer(6)])]);
+
+        let mut packets = packets.clone();
+        packets.push(divider1.clone());
+        packets.push(divider2.clone());
+        packets.sort();
+
+        let idx1 = packets.binary_search(&divider1).unwrap();
+        let idx2 = packets.binary_search(&divider2).unwrap();
+        (idx1 + 1) * (idx2 + 1)
+    }
+}
+
+pub static INPUT: &str = include_str!("input.txt");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EX: &str = include_str!("example.txt");
+
+    #[test]
+    fn test_parser() {
+        let parser = parser::PacketDataParser::new();
+
+        let expr = parser.parse("22").unwrap();
+        assert_eq!(&format!("{:?}", expr), "22");
+
+        let expr = parser.parse("[22]").unwrap();
+        assert_eq!(&format!("{:?}", expr), "[22]");
+
+        let expr = parser.parse("[1,2,3,4]").unwrap();
+        assert_eq!(&format!("{:?}", expr), "[1, 2, 3, 4]");
+
+        let expr = parser.parse("[1,[2,3,4],5,[6,7]]").unwrap();
+        assert_eq!(&format!("{:?}", expr), "[1, [2, 3, 4], 5, [6, 7]]");
+    }
+
+    #[test]
+    fn test_load_packet_pairs() {
+        let packets = load_packets(EX).unwrap();
+        let packet_pairs = packet_pairs(&packets);
+        assert_eq!(packet_pairs.len(), 8);
+        assert_eq!(
+            &format!("{:?}", packet_pairs.first().unwrap().0),
+            "[1, 1, 3, 1, 1]"
+        );
+        assert_eq!(
+            &format!("{:?}", packet_pairs.first().unwrap().1),
+            "[1, 1, 5, 1, 1]"
+        );
+        assert_eq!(
+            &format!("{:?}", packet_pairs.last().unwrap().0),
+            "[1, [2, [3, [4, [5, 6, 7]]]], 8, 9]"
+        );
+        assert_eq!(
+            &format!("{:?}", packet_pairs.last().unwrap().1),
+            "[1, [2, [3, [4, [5, 6, 0]]]], 8, 9]"
+        );
+    }
+
+    #[test]
+    fn test_load_packets() {
+        let packets = load_packets(EX).unwrap();
+        assert_eq!(packets.len(), 16);
+        assert_eq!(
+            &format!("{:?}", packets.first().unwrap()),
+            "[1, 1, 3, 1, 1]"
+        );
+        assert_eq!(
+            &format!("{:?}", packets.last().unwrap()),
+            "[1, [2, [3, [4, [5, 6, 0]]]], 8, 9]"
+        );
+    }
+
+    #[test]
+    fn test_load_invalid_packets() {
+        let err = load_packets("[1,2]\n\n[1,[2,x]]\n").err().unwrap();
+        assert_eq!(err, ParseError::new(3, 7, "invalid token"));
+
+        let err = load_packets("[1,2]\n[1,[2]\n").err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(2, 7, "unexpected end of line, expected \",\" or \"]\"")
+        );
+
+        let err = load_packets("[1,2]]\n").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 6, "unexpected ']'"));
+
+        let err = load_packets("[1,99999999999999999999999]\n").err().unwrap();
+        assert_eq!(err, ParseError::new(1, 4, "number too large"));
+    }
+
+    #[test]
+    fn test_solve_1() {
+        assert_eq!(Day13.part1(&Day13.parse(EX).unwrap()), 13);
+    }
+
+    #[test]
+    fn test_solve_2() {
+        assert_eq!(Day13.part2(&Day13.parse(EX).unwrap()), 140);
+    }
+}
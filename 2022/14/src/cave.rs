@@ -1,35 +1,26 @@
-use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
-use std::ops::Range;
 
-use crate::parser::Paths;
+use aoc_common::diagnostic;
+use aoc_geometry::{Bounds, Point};
+use aoc_grid::Grid;
 
-type Row = VecDeque<Stuff>;
-type Position = (isize, isize);
+use crate::parser::Paths;
 
-#[derive(Debug, Clone)]
-struct Bounds {
-    x: Range<isize>,
-    y: Range<isize>,
-}
+type Position = Point<isize>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Stuff {
-    AIR,
-    ROCK,
-    SAND,
-    INLET,
-    TILDE,
+    Air,
+    Rock,
+    Sand,
+    Inlet,
+    Tilde,
 }
 
-const AIR: u8 = b'.';
-const ROCK: u8 = b'#';
-const SAND: u8 = b'o';
-const INLET: u8 = b'+';
-
 pub struct Cave {
-    data: Vec<Row>,
-    bounds: Bounds,
+    data: Grid<Stuff>,
+    /// The position of the top left cell of `data`
+    origin: Position,
     inlet_pos: Position,
     has_bottom: bool,
 }
@@ -38,11 +29,8 @@ impl Cave {
     /// Creates a cave with the given sand inlet position
     pub fn new(inlet_pos: Position, rocks: Option<&Paths>, has_bottom: bool) -> Cave {
         let mut cave = Cave {
-            data: vec![[Stuff::INLET].into()],
-            bounds: Bounds {
-                x: (inlet_pos.0..inlet_pos.0 + 1).into(),
-                y: (inlet_pos.1..inlet_pos.1 + 1).into(),
-            },
+            data: Grid::new(1, 1, Stuff::Inlet),
+            origin: inlet_pos,
             inlet_pos,
             has_bottom: false,
         };
@@ -60,16 +48,27 @@ impl Cave {
 
     fn put(&mut self, pos: &Position, data: Stuff) {
         self.ensure_bounds(pos);
-        let (row, col) = self.pos_to_idx(pos);
-        self.data[row][col] = data;
+        let idx = self.pos_to_idx(pos);
+        self.data[idx] = data;
     }
 
+    #[cfg(test)]
     fn width(&self) -> usize {
-        self.bounds.x.len()
+        self.data.width()
     }
 
+    #[cfg(test)]
     fn height(&self) -> usize {
-        self.bounds.y.len()
+        self.data.height()
+    }
+
+    fn bounds(&self) -> Bounds<isize> {
+        Bounds::Bounded {
+            xmin: self.origin.x,
+            xmax: self.origin.x + self.data.width() as isize - 1,
+            ymin: self.origin.y,
+            ymax: self.origin.y + self.data.height() as isize - 1,
+        }
     }
 
     fn ensure_bounds(&mut self, pos: &Position) {
@@ -77,90 +76,53 @@ impl Cave {
             return;
         }
 
-        if pos.0 >= self.bounds.x.end {
-            let numcols = pos.0 - self.bounds.x.end + 1;
-            // we need to append columns
-            for y in self.bounds.y.clone() {
-                let row = self.y_to_row(y);
-                for _ in 0..numcols {
-                    if self.has_bottom && y == self.bounds.y.end - 1 {
-                        self.data[row].push_back(Stuff::ROCK);
-                    } else {
-                        self.data[row].push_back(Stuff::AIR);
-                    }
-                }
-            }
-        } else if pos.0 < self.bounds.x.start {
-            // we need to prepend columns
-            let numcols = self.bounds.x.start - pos.0;
-            for y in self.bounds.y.clone() {
-                let row = self.y_to_row(y);
-                for _ in 0..numcols {
-                    if self.has_bottom && y == self.bounds.y.end - 1 {
-                        self.data[row].push_front(Stuff::ROCK);
-                    } else {
-                        self.data[row].push_front(Stuff::AIR);
-                    }
-                }
-            }
-        }
-
-        let new_x_range = self.bounds.x.start.min(pos.0)..self.bounds.x.end.max(pos.0 + 1);
-
-        if pos.1 >= self.bounds.y.end {
-            let numrows = pos.1 - self.bounds.y.end + 1;
-            // we need to append rows
-            let row_of_air = (0..new_x_range.len()).map(|_| Stuff::AIR).collect::<VecDeque<_>>();
-            for _ in 0..numrows {
-                self.data.push(row_of_air.clone());
-            }
-        } else if pos.1 < self.bounds.y.start {
+        if pos.y < self.origin.y {
             panic!("Prepending rows is not allowed!");
         }
 
-        let new_y_range = self.bounds.y.start.min(pos.1)..self.bounds.y.end.max(pos.1 + 1);
-
-        self.bounds = Bounds {
-            x: new_x_range,
-            y: new_y_range,
+        let (xmin, xmax, _, ymax) = self.bounds().to_tuple().unwrap();
+        let left = (xmin - pos.x).max(0) as usize;
+        let right = (pos.x - xmax).max(0) as usize;
+        let bottom = (pos.y - ymax).max(0) as usize;
+        let bottom_row = self.data.height() - 1;
+        self.data.expand(0, right, bottom, left, Stuff::Air);
+        self.origin.x = self.origin.x.min(pos.x);
+
+        if self.has_bottom {
+            // the floor extends infinitely, so new columns get their piece of it
+            let width = self.data.width();
+            for col in (0..left).chain(width - right..width) {
+                self.data[(bottom_row, col)] = Stuff::Rock;
+            }
         }
     }
 
     fn contains(&self, pos: &Position) -> bool {
-        self.bounds.x.contains(&pos.0) && self.bounds.y.contains(&pos.1)
+        self.bounds().contains(*pos)
     }
 
     fn pos_to_idx(&self, pos: &Position) -> (usize, usize) {
         debug_assert!(self.contains(pos), "position out of bounds");
-        (self.y_to_row(pos.1), (self.x_to_col(pos.0)))
-    }
-
-    fn y_to_row(&self, y: isize) -> usize {
-        debug_assert!(self.bounds.y.contains(&y), "y out of bounds");
-        (y - self.bounds.y.start) as usize
-    }
-
-    fn x_to_col(&self, x: isize) -> usize {
-        debug_assert!(self.bounds.x.contains(&x), "x out of bounds");
-        (x - self.bounds.x.start) as usize
+        let offset = *pos - self.origin;
+        (offset.y as usize, offset.x as usize)
     }
 
     fn get(&mut self, pos: &Position) -> Stuff {
         self.ensure_bounds(pos);
-        let (row, col) = self.pos_to_idx(pos);
-        self.data[row][col]
+        self.data[self.pos_to_idx(pos)]
     }
 
     fn add_rocks(&mut self, rocks: &Paths) {
         for p in rocks.points_iter() {
-            self.put(&(p.x() as isize, p.y() as isize), Stuff::ROCK)
+            let p = p.try_cast().expect("rock position out of range");
+            self.put(&p, Stuff::Rock)
         }
     }
 
     fn add_bottom(&mut self) {
-        let bottom_y = self.bounds.y.end + 1;
-        for x in self.bounds.x.clone() {
-            self.put(&(x, bottom_y), Stuff::ROCK);
+        let (xmin, xmax, _, ymax) = self.bounds().to_tuple().unwrap();
+        for x in xmin..=xmax {
+            self.put(&Position::new(x, ymax + 2), Stuff::Rock);
         }
         self.has_bottom = true;
     }
@@ -168,26 +130,25 @@ impl Cave {
     pub fn drop_sand(&mut self) -> bool {
         let mut pos = self.inlet_pos;
         let mut old_pos = pos;
-        while pos.1 + 1 < self.bounds.y.end {
+        while pos.y < self.bounds().ymax().unwrap() {
             old_pos = pos;
-            if !self.check_pos(&mut pos, (0, 1)) {
-                if !self.check_pos(&mut pos, (-1, 1)) {
-                    if !self.check_pos(&mut pos, (1, 1)) {
-                        self.put(&pos, Stuff::SAND);
-                        return pos != self.inlet_pos;
-                    }
-                }
+            if !self.check_pos(&mut pos, Position::new(0, 1))
+                && !self.check_pos(&mut pos, Position::new(-1, 1))
+                && !self.check_pos(&mut pos, Position::new(1, 1))
+            {
+                self.put(&pos, Stuff::Sand);
+                return pos != self.inlet_pos;
             }
         }
 
-        println!("Sand did not stop at {:?}", old_pos);
-        self.put(&old_pos, Stuff::TILDE);
+        diagnostic!("Sand did not stop at {:?}", old_pos);
+        self.put(&old_pos, Stuff::Tilde);
         false
     }
 
-    fn check_pos(&mut self, pos: &mut Position, delta: (isize, isize)) -> bool {
-        let new_pos = (pos.0 + delta.0, pos.1 + delta.1);
-        if self.get(&new_pos) == Stuff::AIR {
+    fn check_pos(&mut self, pos: &mut Position, delta: Position) -> bool {
+        let new_pos = *pos + delta;
+        if self.get(&new_pos) == Stuff::Air {
             *pos = new_pos;
             true
         } else {
@@ -196,21 +157,22 @@ impl Cave {
     }
 }
 
+impl Display for Stuff {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let c = match self {
+            Stuff::Air => '.',
+            Stuff::Rock => '#',
+            Stuff::Sand => 'o',
+            Stuff::Inlet => '+',
+            Stuff::Tilde => '~',
+        };
+        write!(f, "{c}")
+    }
+}
+
 impl Display for Cave {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        for row in self.data.iter() {
-            for stuff in row.iter() {
-                match *stuff {
-                    Stuff::AIR => write!(f, ".")?,
-                    Stuff::ROCK => write!(f, "#")?,
-                    Stuff::SAND => write!(f, "o")?,
-                    Stuff::INLET => write!(f, "+")?,
-                    Stuff::TILDE => write!(f, "~")?,
-                }
-            }
-            write!(f, "\n")?;
-        }
-        Ok(())
+        write!(f, "{}", self.data)
     }
 }
 
@@ -220,59 +182,59 @@ mod tests {
 
     #[test]
     fn new_cave_contains_just_the_inlet() {
-        let mut c = Cave::new((500, 0), None, false);
+        let mut c = Cave::new(Position::new(500, 0), None, false);
         assert_eq!(c.width(), 1);
         assert_eq!(c.height(), 1);
-        assert_eq!(c.get(&(500, 0)), Stuff::INLET);
+        assert_eq!(c.get(&Position::new(500, 0)), Stuff::Inlet);
     }
 
     #[test]
     fn write_access_appends_air_columns() {
-        let mut c = Cave::new((500, 0), None, false);
-        c.put(&(600, 0), Stuff::ROCK);
+        let mut c = Cave::new(Position::new(500, 0), None, false);
+        c.put(&Position::new(600, 0), Stuff::Rock);
         assert_eq!(c.width(), 101);
         assert_eq!(c.height(), 1);
-        assert_eq!(c.get(&(600, 0)), Stuff::ROCK);
-        assert_eq!(c.get(&(550, 0)), Stuff::AIR);
-        assert_eq!(c.get(&(500, 0)), Stuff::INLET);
+        assert_eq!(c.get(&Position::new(600, 0)), Stuff::Rock);
+        assert_eq!(c.get(&Position::new(550, 0)), Stuff::Air);
+        assert_eq!(c.get(&Position::new(500, 0)), Stuff::Inlet);
     }
 
     #[test]
     fn write_access_prepends_air_columns() {
-        let mut c = Cave::new((500, 0), None, false);
-        c.put(&(-600, 0), Stuff::ROCK);
+        let mut c = Cave::new(Position::new(500, 0), None, false);
+        c.put(&Position::new(-600, 0), Stuff::Rock);
         assert_eq!(c.width(), 1101);
         assert_eq!(c.height(), 1);
-        assert_eq!(c.get(&(-600, 0)), Stuff::ROCK);
-        assert_eq!(c.get(&(0, 0)), Stuff::AIR);
-        assert_eq!(c.get(&(500, 0)), Stuff::INLET);
+        assert_eq!(c.get(&Position::new(-600, 0)), Stuff::Rock);
+        assert_eq!(c.get(&Position::new(0, 0)), Stuff::Air);
+        assert_eq!(c.get(&Position::new(500, 0)), Stuff::Inlet);
     }
 
     #[test]
     fn write_access_appends_air_rows() {
-        let mut c = Cave::new((500, 0), None, false);
-        c.put(&(500, 10), Stuff::ROCK);
+        let mut c = Cave::new(Position::new(500, 0), None, false);
+        c.put(&Position::new(500, 10), Stuff::Rock);
         assert_eq!(c.width(), 1);
         assert_eq!(c.height(), 11);
-        assert_eq!(c.get(&(500, 10)), Stuff::ROCK);
-        assert_eq!(c.get(&(500, 5)), Stuff::AIR);
-        assert_eq!(c.get(&(500, 0)), Stuff::INLET);
+        assert_eq!(c.get(&Position::new(500, 10)), Stuff::Rock);
+        assert_eq!(c.get(&Position::new(500, 5)), Stuff::Air);
+        assert_eq!(c.get(&Position::new(500, 0)), Stuff::Inlet);
     }
 
     #[test]
     fn write_access_appends_air_rows_and_columns() {
-        let mut c = Cave::new((500, 0), None, false);
-        c.put(&(600, 10), Stuff::ROCK);
+        let mut c = Cave::new(Position::new(500, 0), None, false);
+        c.put(&Position::new(600, 10), Stuff::Rock);
         assert_eq!(c.width(), 101);
         assert_eq!(c.height(), 11);
-        assert_eq!(c.get(&(600, 10)), Stuff::ROCK);
-        assert_eq!(c.get(&(500, 5)), Stuff::AIR);
-        assert_eq!(c.get(&(500, 0)), Stuff::INLET);
+        assert_eq!(c.get(&Position::new(600, 10)), Stuff::Rock);
+        assert_eq!(c.get(&Position::new(500, 5)), Stuff::Air);
+        assert_eq!(c.get(&Position::new(500, 0)), Stuff::Inlet);
     }
 
     #[test]
     fn get_access_appends_air_rows() {
-        let mut c = Cave::new((500, 0), None, false);
-        assert_eq!(c.get(&(500, 10)), Stuff::AIR);
+        let mut c = Cave::new(Position::new(500, 0), None, false);
+        assert_eq!(c.get(&Position::new(500, 10)), Stuff::Air);
     }
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+/// Generates `size` rock paths of horizontal and vertical segments below the sand inlet.
+pub fn generate(seed: u64, size: usize) -> String {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let depth = 10 + size as i32 * 2;
+    let mut paths = String::new();
+    for _ in 0..size {
+        // the inlet is at 500,0, so rocks start one row below it; x can't go
+        // negative either, since the cave is wider than 1000 for large sizes
+        let mut x = rng.gen_range((500 - depth).max(0)..=500 + depth);
+        let mut y = rng.gen_range(1..=depth);
+        let mut points = vec![format!("{x},{y}")];
+        let mut horizontal = rng.gen_bool(0.5);
+        for _ in 0..rng.gen_range(1..=4) {
+            let length = rng.gen_range(1..=8);
+            if horizontal {
+                x = (x + if rng.gen_bool(0.5) { length } else { -length }).max(0);
+            } else {
+                y = (y + if rng.gen_bool(0.5) { length } else { -length }).max(1);
+            }
+            points.push(format!("{x},{y}"));
+            horizontal = !horizontal;
+        }
+        paths.push_str(&points.join(" -> "));
+        paths.push('\n');
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::Day14;
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(14, 20), generate(14, 20));
+        assert_ne!(generate(14, 20), generate(15, 20));
+    }
+
+    #[test]
+    fn generated_paths_can_be_solved() {
+        for seed in 0..10 {
+            let rocks = Day14.parse(&generate(seed, seed as usize * 3)).unwrap();
+            assert!(Day14.part1(&rocks) < Day14.part2(&rocks));
+        }
+    }
+
+    #[test]
+    fn large_sizes_stay_in_bounds() {
+        assert!(Day14.parse(&generate(14, 1000)).is_ok());
+    }
+}
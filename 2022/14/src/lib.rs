@@ -0,0 +1,88 @@
+use aoc_common::{diagnostic, ParseError, Solution};
+use aoc_geometry::Point;
+
+use crate::cave::Cave;
+use crate::parser::Paths;
+
+pub mod cave;
+pub mod generate;
+pub mod parser;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Paths;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(&self, input: &str) -> Result<Paths, ParseError> {
+        let (_, rocks) = parser::parse(input).map_err(|err| ParseError::from_nom(input, err))?;
+        match rocks.find_unaligned() {
+            // every path is on a line of its own
+            Some(idx) => Err(ParseError::new(
+                idx + 1,
+                1,
+                "path is not horizontal or vertical",
+            )),
+            None => Ok(rocks),
+        }
+    }
+
+    fn part1(&self, rocks: &Paths) -> usize {
+        let mut cave = Cave::new(Point::new(500, 0), Some(rocks), false);
+        let mut counter = 0;
+        while cave.drop_sand() {
+            counter += 1;
+        }
+        diagnostic!("{cave}");
+        counter
+    }
+
+    fn part2(&self, rocks: &Paths) -> usize {
+        let mut cave = Cave::new(Point::new(500, 0), Some(rocks), true);
+        let mut counter = 0;
+
+        while cave.drop_sand() {
+            counter += 1;
+        }
+        diagnostic!("{cave}");
+        counter + 1
+    }
+}
+
+pub static INPUT: &str = include_str!("input.txt");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EX: &str = include_str!("example.txt");
+
+    #[test]
+    fn invalid_input() {
+        let err = Day14
+            .parse("498,4 -> 498,6\n503,4 -> 502;4\n")
+            .err()
+            .unwrap();
+        assert_eq!(err, ParseError::new(2, 13, "unexpected input (Char)"));
+
+        let err = Day14
+            .parse("498,4 -> 498,6\n503,4 -> 502,9\n")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "path is not horizontal or vertical")
+        );
+    }
+
+    #[test]
+    fn example1() {
+        assert_eq!(Day14.part1(&Day14.parse(EX).unwrap()), 24);
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(Day14.part2(&Day14.parse(EX).unwrap()), 93);
+    }
+}
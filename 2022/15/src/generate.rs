@@ -0,0 +1,157 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::parser::Point;
+
+/// The search area of part two, the same as in the actual puzzle.
+const EXTENT: i32 = 4_000_000;
+
+/// Converts coordinates rotated by 45 degrees, `u = x + y` and `v = x - y`, back. Manhattan
+/// circles are squares in rotated coordinates, which makes them easy to place.
+fn unrotate(u: i32, v: i32) -> Point {
+    Point::new((u + v) / 2, (u - v) / 2)
+}
+
+fn format_reading(sensor: Point, beacon: Point) -> String {
+    format!(
+        "Sensor at x={}, y={}: closest beacon is at x={}, y={}\n",
+        sensor.x, sensor.y, beacon.x, beacon.y
+    )
+}
+
+/// Generates readings that leave exactly one spot of the search area `0..=extent` uncovered
+/// and returns them with that spot.
+///
+/// Four sensors cover everything but the distress beacon: in rotated coordinates, one covers
+/// all of the search area left of it, one all right of it, and two the rest above and below.
+/// Their beacons are on the far sides, outside of the other sensors' ranges. Then `size` more
+/// sensors are added far around the search area, each closer to its beacon than to the
+/// distress beacon. Like in the puzzle, the beacon reported by a sensor is the unique closest
+/// one, so no sensor has a beacon within its range.
+fn readings(seed: u64, size: usize, extent: i32) -> (String, Point) {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+    let distress = Point::new(rng.gen_range(0..=extent), rng.gen_range(0..=extent));
+    let (u, v) = (distress.x + distress.y, distress.x - distress.y);
+
+    // large enough to reach across the whole search area from next to the distress beacon
+    let radius = 3 * extent;
+    let mut sensors = Vec::new();
+    let mut beacons = Vec::new();
+    for sign in [-1, 1] {
+        let center = sign * (radius + 1);
+        sensors.push((unrotate(u + center, v - 1), radius));
+        beacons.push(unrotate(u + center + sign * radius, v - 1));
+        sensors.push((unrotate(u - 1, v + center), radius));
+        beacons.push(unrotate(u - 1, v + center + sign * radius));
+    }
+
+    // the other beacons have to be outside of the four ranges, which reach far beyond the
+    // search area
+    let spread = |rng: &mut ChaCha8Rng| {
+        let range = -8 * extent..=9 * extent;
+        Point::new(rng.gen_range(range.clone()), rng.gen_range(range))
+    };
+    while beacons.len() < 4 + size / 3 + 1 {
+        let beacon = spread(&mut rng);
+        let covered = sensors
+            .iter()
+            .any(|&(sensor, radius)| sensor.manhattan_distance(beacon) <= radius);
+        if !covered {
+            beacons.push(beacon);
+        }
+    }
+
+    let mut readings: String = sensors
+        .iter()
+        .zip(&beacons)
+        .map(|(&(sensor, _), &beacon)| format_reading(sensor, beacon))
+        .collect();
+    let mut count = 0;
+    while count < size {
+        let sensor = spread(&mut rng);
+        let mut distances: Vec<_> = beacons
+            .iter()
+            .map(|beacon| (sensor.manhattan_distance(*beacon), *beacon))
+            .collect();
+        distances.sort_by_key(|(distance, _)| *distance);
+        let (distance, beacon) = distances[0];
+        if distances[1].0 == distance || distance >= sensor.manhattan_distance(distress) {
+            continue;
+        }
+        readings.push_str(&format_reading(sensor, beacon));
+        count += 1;
+    }
+    (readings, distress)
+}
+
+/// Generates readings of `size` sensors, plus four that leave the distress beacon as the
+/// only uncovered spot of the search area of the actual puzzle.
+pub fn generate(seed: u64, size: usize) -> String {
+    readings(seed, size, EXTENT).0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use aoc_common::Solution;
+
+    use super::*;
+    use crate::parser::Sensor;
+    use crate::{tuning_frequency, Day15};
+
+    #[test]
+    fn generator_is_deterministic() {
+        assert_eq!(generate(15, 20), generate(15, 20));
+        assert_ne!(generate(15, 20), generate(16, 20));
+    }
+
+    #[test]
+    fn generated_readings_can_be_parsed() {
+        for seed in 0..10 {
+            let size = seed as usize * 5;
+            let sensors = Day15::PUZZLE.parse(&generate(seed, size)).unwrap();
+            assert_eq!(sensors.len(), 4 + size);
+        }
+    }
+
+    #[test]
+    fn generated_readings_hide_one_beacon() {
+        for seed in 0..20 {
+            let extent = 20 + seed as i32 * 10;
+            let (input, distress) = readings(seed, seed as usize * 2, extent);
+            let day = Day15 {
+                row: extent / 2,
+                max_coord: extent,
+            };
+            let sensors = day.parse(&input).unwrap();
+            assert_eq!(
+                day.part2(&sensors),
+                Ok(tuning_frequency(distress)),
+                "{input}"
+            );
+
+            let uncovered: Vec<Point> = (0..=extent)
+                .flat_map(|y| (0..=extent).map(move |x| Point::new(x, y)))
+                .filter(|&point| {
+                    let covers = |sensor: &Sensor| {
+                        sensor
+                            .range(point.y)
+                            .is_some_and(|range| range.contains(&point.x))
+                    };
+                    !sensors.iter().any(covers)
+                })
+                .collect();
+            assert_eq!(uncovered, [distress]);
+
+            let beacons: HashSet<Point> = sensors.iter().map(Sensor::closest_beacon).collect();
+            let row: HashSet<i32> = sensors
+                .iter()
+                .flat_map(|sensor| sensor.range(day.row))
+                .flatten()
+                .filter(|&x| !beacons.contains(&Point::new(x, day.row)))
+                .collect();
+            assert_eq!(day.part1(&sensors), row.len());
+        }
+    }
+}
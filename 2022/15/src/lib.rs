@@ -0,0 +1,300 @@
+use std::ops::RangeInclusive;
+
+use aoc_common::{diagnostic, ParseError, Solution};
+
+use crate::parser::{parse, Point, Sensor};
+
+pub mod generate;
+pub mod parser;
+
+/// Counts the positions in row `y` where no beacon can be, merging the ranges instead of
+/// looking at every position, as they can be millions of positions wide.
+fn count_positions_without_beacon(sensors: &[Sensor], y: i32) -> usize {
+    let mut ranges: Vec<RangeInclusive<i32>> = sensors
+        .iter()
+        .flat_map(|sensor| sensor.positions_with_no_beacons(y))
+        .collect();
+    ranges.sort_by_key(|range| *range.start());
+    let mut count = 0;
+    let mut end = None;
+    for range in ranges {
+        let start = match end {
+            Some(end) if end >= *range.end() => continue,
+            Some(end) if end >= *range.start() => end + 1,
+            _ => *range.start(),
+        };
+        count += (range.end() - start + 1) as usize;
+        end = Some(*range.end());
+    }
+    count
+}
+
+fn process_ranges(mut ranges: Vec<RangeInclusive<i32>>, max: i32) -> Vec<RangeInclusive<i32>> {
+    if ranges.is_empty() {
+        return Vec::new();
+    }
+
+    if ranges.len() > 1 {
+        ranges.sort_by(|a, b| a.start().cmp(b.start()));
+    }
+
+    let mut res = Vec::new();
+    let mut cur: Option<RangeInclusive<i32>> = None;
+    for range in ranges {
+        if *range.end() < 0 {
+            continue;
+        }
+
+        if *range.start() > max {
+            break;
+        }
+
+        if let Some(c) = &cur {
+            if *range.start() <= *c.end() + 1 {
+                // merge this range into the current one
+                cur = Some(*c.start()..=*range.end().clamp(c.end(), &max));
+            } else {
+                res.push(c.clone());
+                cur = Some(*range.start().max(&0)..=*range.end().min(&max));
+            }
+        } else {
+            cur = Some(*range.start().max(&0)..=*range.end().min(&max));
+        }
+    }
+
+    if let Some(c) = cur {
+        res.push(c);
+    }
+
+    res
+}
+
+/// Finds the only position in the search area that no sensor covers and returns its tuning
+/// frequency, or an error after the last sensor if there is no such position.
+fn find_distress_beacon(sensors: &[Sensor], max_coord: i32) -> Result<u64, ParseError> {
+    let mut pos: Option<Point> = None;
+    for y in 0..=max_coord {
+        let ranges = sensors
+            .iter()
+            .flat_map(|sensor| sensor.range(y))
+            .collect::<Vec<_>>();
+
+        let ranges = process_ranges(ranges, max_coord);
+        if ranges.is_empty() {
+            continue;
+        }
+        // We're looking for exactly on free spot. So either we've one range with starts or
+        // ends just before the boundary or we have exactly two ranges with a gap of one between them.
+        let x = if ranges.len() == 2 && *ranges[1].start() == *ranges[0].end() + 2 {
+            *ranges[0].end() + 1
+        } else if *ranges[0].start() == 1 {
+            0
+        } else if *ranges[0].end() == max_coord - 1 {
+            max_coord
+        } else {
+            continue;
+        };
+        pos = Some(Point::new(x, y));
+        diagnostic!("Found: {ranges:?} pos={:?}", pos.unwrap());
+    }
+    pos.map(tuning_frequency).ok_or_else(|| {
+        ParseError::new(
+            sensors.len() + 1,
+            1,
+            "no position is left for the distress beacon",
+        )
+    })
+}
+
+fn tuning_frequency(beacon: Point) -> u64 {
+    beacon.x as u64 * 4000000 + beacon.y as u64
+}
+
+/// Day 15 is parameterized by the row inspected in part one and the size of the search area
+/// in part two, which differ between the example and the actual puzzle.
+pub struct Day15 {
+    row: i32,
+    max_coord: i32,
+}
+
+impl Day15 {
+    pub const PUZZLE: Day15 = Day15 {
+        row: 2000000,
+        max_coord: 4000000,
+    };
+}
+
+impl Solution for Day15 {
+    type Input = Vec<Sensor>;
+    type Answer1 = usize;
+    type Answer2 = Result<u64, ParseError>;
+
+    fn parse(&self, input: &str) -> Result<Vec<Sensor>, ParseError> {
+        parse(input)
+            .map(|(_, sensors)| sensors)
+            .map_err(|err| ParseError::from_nom(input, err))
+    }
+
+    fn part1(&self, sensors: &Vec<Sensor>) -> usize {
+        count_positions_without_beacon(sensors, self.row)
+    }
+
+    fn part2(&self, sensors: &Vec<Sensor>) -> Result<u64, ParseError> {
+        find_distress_beacon(sensors, self.max_coord)
+    }
+}
+
+pub static INPUT: &str = include_str!("input.txt");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    static EX1: &str = include_str!("example.txt");
+
+    const EXAMPLE: Day15 = Day15 {
+        row: 10,
+        max_coord: 20,
+    };
+
+    #[test]
+    fn invalid_input() {
+        let input = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15\n\
+                     Sensor at x=9, y=16: closest beacon at x=10, y=16\n";
+        let err = EXAMPLE.parse(input).err().unwrap();
+        assert_eq!(err, ParseError::new(2, 20, "unexpected input (Tag)"));
+    }
+
+    #[test]
+    fn example1() {
+        assert_eq!(EXAMPLE.part1(&EXAMPLE.parse(EX1).unwrap()), 26);
+    }
+
+    #[test]
+    fn example2() {
+        assert_eq!(EXAMPLE.part2(&EXAMPLE.parse(EX1).unwrap()), Ok(56000011));
+    }
+
+    #[test]
+    fn no_distress_beacon() {
+        let err = ParseError::new(1, 1, "no position is left for the distress beacon");
+        assert_eq!(EXAMPLE.part2(&EXAMPLE.parse("").unwrap()), Err(err));
+        let input = "Sensor at x=10, y=10: closest beacon is at x=30, y=10\n";
+        let err = ParseError::new(2, 1, "no position is left for the distress beacon");
+        assert_eq!(EXAMPLE.part2(&EXAMPLE.parse(input).unwrap()), Err(err));
+    }
+}
+
+#[cfg(test)]
+mod range_tests {
+    use super::*;
+
+    #[test]
+    fn test_process_ranges_1() {
+        let ranges = vec![0..=10, 20..=30];
+        assert_eq!(process_ranges(ranges, 50), [0..=10, 20..=30]);
+    }
+
+    #[test]
+    fn test_process_ranges_2() {
+        let ranges = vec![-20..=-5, 0..=10, 20..=30];
+        assert_eq!(process_ranges(ranges, 50), [0..=10, 20..=30]);
+    }
+
+    #[test]
+    fn test_process_ranges_3() {
+        let ranges = vec![-20..=-5, 0..=10, 20..=30, 60..=100];
+        assert_eq!(process_ranges(ranges, 50), [0..=10, 20..=30]);
+    }
+
+    #[test]
+    fn test_process_ranges_4() {
+        let ranges = vec![-10..=40];
+        assert_eq!(process_ranges(ranges, 50), [0..=40]);
+    }
+
+    #[test]
+    fn test_process_ranges_5() {
+        let ranges = vec![-10..=60];
+        assert_eq!(process_ranges(ranges, 50), [0..=50]);
+    }
+
+    #[test]
+    fn test_process_ranges_6() {
+        let ranges = vec![0..=10, 5..=30];
+        assert_eq!(process_ranges(ranges, 50), [0..=30]);
+    }
+
+    #[test]
+    fn test_process_ranges_7() {
+        let ranges = vec![0..=10, 5..=30, 17..=20];
+        assert_eq!(process_ranges(ranges, 50), [0..=30]);
+    }
+
+    #[test]
+    fn test_process_ranges_8() {
+        let ranges = vec![
+            -3..=7,
+            8..=10,
+            10..=14,
+            10..=10,
+            10..=18,
+            8..=8,
+            14..=26,
+            15..=19,
+        ];
+        assert_eq!(process_ranges(ranges, 20), [0..=20]);
+    }
+
+    #[test]
+    fn test_process_ranges_9() {
+        let ranges = vec![2..=2, 11..=13, 3..=13, -3..=3, 15..=25, 15..=17];
+        assert_eq!(process_ranges(ranges, 20), [0..=13, 15..=20]);
+    }
+}
+
+#[cfg(test)]
+mod range_properties {
+    use std::collections::BTreeSet;
+
+    use proptest::prelude::*;
+
+    use super::*;
+
+    /// Coverage of `0..=max` computed cell by cell.
+    fn covered_cells(ranges: &[RangeInclusive<i32>], max: i32) -> BTreeSet<i32> {
+        ranges
+            .iter()
+            .flat_map(|range| range.clone())
+            .filter(|x| (0..=max).contains(x))
+            .collect()
+    }
+
+    fn ranges_strategy() -> impl Strategy<Value = Vec<RangeInclusive<i32>>> {
+        // like the ranges covered by sensors, which are never empty
+        prop::collection::vec(
+            (-30..60, 0..25).prop_map(|(start, len)| start..=start + len),
+            0..10,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn process_ranges_covers_the_same_cells(ranges in ranges_strategy(), max in 0..50) {
+            let merged = process_ranges(ranges.clone(), max);
+            prop_assert_eq!(covered_cells(&merged, max), covered_cells(&ranges, max));
+        }
+
+        #[test]
+        fn process_ranges_returns_separated_ranges(ranges in ranges_strategy(), max in 0..50) {
+            let merged = process_ranges(ranges, max);
+            for range in &merged {
+                prop_assert!(0 <= *range.start() && range.start() <= range.end() && *range.end() <= max);
+            }
+            // find_distress_beacon relies on gaps between the ranges
+            for pair in merged.windows(2) {
+                prop_assert!(*pair[1].start() > *pair[0].end() + 1);
+            }
+        }
+    }
+}
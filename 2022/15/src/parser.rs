@@ -3,24 +3,13 @@ use std::ops::RangeInclusive;
 use nom::{
     bytes::complete::tag,
     character::{complete::i32 as parse_i32, complete::newline},
-    combinator::all_consuming,
+    combinator::{all_consuming, cut},
     multi::many0,
     sequence::terminated,
     IResult,
 };
 
-#[derive(Copy, Clone, Eq, PartialEq, Debug, Hash)]
-pub struct Point(pub i32, pub i32);
-
-impl Point {
-    fn distance_to(&self, rhs: &Point) -> i32 {
-        (self.0 - rhs.0).abs() + (self.1 - rhs.1).abs()
-    }
-
-    pub fn tuning_frequency(&self) -> u64 {
-        self.0 as u64 * 4000000 + self.1 as u64
-    }
-}
+pub type Point = aoc_geometry::Point<i32>;
 
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub struct Sensor {
@@ -34,27 +23,31 @@ impl Sensor {
         Sensor {
             pos,
             closest_beacon: beacon,
-            distance: pos.distance_to(&beacon),
+            distance: pos.manhattan_distance(beacon),
         }
     }
 
+    pub fn closest_beacon(&self) -> Point {
+        self.closest_beacon
+    }
+
     pub fn range(&self, y: i32) -> Option<RangeInclusive<i32>> {
-        let dx = self.distance - (y - self.pos.1).abs();
+        let dx = self.distance - (y - self.pos.y).abs();
         if dx < 0 {
             None
         } else {
-            Some(self.pos.0 - dx..=self.pos.0 + dx)
+            Some(self.pos.x - dx..=self.pos.x + dx)
         }
     }
 
     pub fn positions_with_no_beacons(&self, y: i32) -> Option<RangeInclusive<i32>> {
-        let dx = self.distance - (y - self.pos.1).abs();
+        let dx = self.distance - (y - self.pos.y).abs();
         if dx < 0 {
             None
-        } else if y == self.closest_beacon.1 {
-            let beacon_x = self.closest_beacon.0;
-            let min_x = self.pos.0 - dx;
-            let max_x = self.pos.0 + dx;
+        } else if y == self.closest_beacon.y {
+            let beacon_x = self.closest_beacon.x;
+            let min_x = self.pos.x - dx;
+            let max_x = self.pos.x + dx;
             if beacon_x < max_x {
                 Some(beacon_x + 1..=max_x)
             } else if beacon_x > min_x {
@@ -63,17 +56,22 @@ impl Sensor {
                 None
             }
         } else {
-            Some(self.pos.0 - dx..=self.pos.0 + dx)
+            Some(self.pos.x - dx..=self.pos.x + dx)
         }
     }
 }
 
 pub fn parse(input: &str) -> IResult<&str, Vec<Sensor>> {
-    all_consuming(many0(terminated(sensor_reading, newline)))(input)
+    all_consuming(many0(terminated(sensor_reading, cut(newline))))(input)
 }
 
 fn sensor_reading(input: &str) -> IResult<&str, Sensor> {
     let (input, _) = tag("Sensor at x=")(input)?;
+    // once a reading has started, errors are no longer recoverable
+    cut(sensor_coordinates)(input)
+}
+
+fn sensor_coordinates(input: &str) -> IResult<&str, Sensor> {
     let (input, pos_x) = parse_i32(input)?;
     let (input, _) = tag(", y=")(input)?;
     let (input, pos_y) = parse_i32(input)?;
@@ -83,7 +81,7 @@ fn sensor_reading(input: &str) -> IResult<&str, Sensor> {
     let (input, beacon_y) = parse_i32(input)?;
     Ok((
         input,
-        Sensor::new(Point(pos_x, pos_y), Point(beacon_x, beacon_y)),
+        Sensor::new(Point::new(pos_x, pos_y), Point::new(beacon_x, beacon_y)),
     ))
 }
 
@@ -95,7 +93,7 @@ mod tests {
     fn parse_sensor_reading() {
         let (_, reading) =
             sensor_reading("Sensor at x=13, y=-2: closest beacon is at x=15, y=3").unwrap();
-        assert_eq!(reading, Sensor::new(Point(13, -2), Point(15, 3)));
+        assert_eq!(reading, Sensor::new(Point::new(13, -2), Point::new(15, 3)));
     }
 
     #[test]
@@ -105,37 +103,37 @@ mod tests {
         assert_eq!(
             reading,
             [
-                Sensor::new(Point(13, -2), Point(15, 3)),
-                Sensor::new(Point(14, 17), Point(10, 16)),
+                Sensor::new(Point::new(13, -2), Point::new(15, 3)),
+                Sensor::new(Point::new(14, 17), Point::new(10, 16)),
             ]
         )
     }
 
     #[test]
     fn point_distance_x_and_y_different() {
-        assert_eq!(Point(8, 7).distance_to(&Point(2, 10)), 9);
+        assert_eq!(Point::new(8, 7).manhattan_distance(Point::new(2, 10)), 9);
     }
 
     #[test]
     fn point_distance_equal_points() {
-        assert_eq!(Point(8, 7).distance_to(&Point(8, 7)), 0);
+        assert_eq!(Point::new(8, 7).manhattan_distance(Point::new(8, 7)), 0);
     }
 
     #[test]
     fn point_distance_x_different() {
-        assert_eq!(Point(8, 7).distance_to(&Point(9, 7)), 1);
-        assert_eq!(Point(8, 7).distance_to(&Point(7, 7)), 1);
+        assert_eq!(Point::new(8, 7).manhattan_distance(Point::new(9, 7)), 1);
+        assert_eq!(Point::new(8, 7).manhattan_distance(Point::new(7, 7)), 1);
     }
 
     #[test]
     fn point_distance_y_different() {
-        assert_eq!(Point(8, 7).distance_to(&Point(8, 8)), 1);
-        assert_eq!(Point(8, 7).distance_to(&Point(8, 6)), 1);
+        assert_eq!(Point::new(8, 7).manhattan_distance(Point::new(8, 8)), 1);
+        assert_eq!(Point::new(8, 7).manhattan_distance(Point::new(8, 6)), 1);
     }
 
     #[test]
     fn sensor_covered_range_for_x_sensor_below() {
-        let sensor = Sensor::new(Point(0, 0), Point(0, 100));
+        let sensor = Sensor::new(Point::new(0, 0), Point::new(0, 100));
         assert_eq!(sensor.positions_with_no_beacons(1000), None);
         assert_eq!(sensor.positions_with_no_beacons(0), Some(-100..=100));
         assert_eq!(sensor.positions_with_no_beacons(100), None);
@@ -144,7 +142,7 @@ mod tests {
 
     #[test]
     fn sensor_covered_range_for_x_sensor_right() {
-        let sensor = Sensor::new(Point(0, 0), Point(100, 0));
+        let sensor = Sensor::new(Point::new(0, 0), Point::new(100, 0));
         assert_eq!(sensor.positions_with_no_beacons(1000), None);
         assert_eq!(sensor.positions_with_no_beacons(0), Some(-100..=99));
         assert_eq!(sensor.positions_with_no_beacons(100), Some(0..=0));
@@ -153,7 +151,7 @@ mod tests {
 
     #[test]
     fn sensor_covered_range_for_x_sensor_right_below() {
-        let sensor = Sensor::new(Point(0, 0), Point(100, 100));
+        let sensor = Sensor::new(Point::new(0, 0), Point::new(100, 100));
         assert_eq!(sensor.positions_with_no_beacons(1000), None);
         assert_eq!(sensor.positions_with_no_beacons(0), Some(-200..=200));
         assert_eq!(sensor.positions_with_no_beacons(100), Some(-100..=99));
@@ -0,0 +1,248 @@
+//! Runs the solutions repeatedly and compares their timings with a stored baseline.
+//!
+//! A baseline file has one line per day and phase with the median time in nanoseconds,
+//! e.g. `12 part2 81234567`.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use std::time::Duration;
+
+use aoc_common::{parse_lines, ParseError, Timings};
+
+use crate::days::Day;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Phase {
+    const ALL: [Phase; 3] = [Phase::Parse, Phase::Part1, Phase::Part2];
+
+    fn of(self, timings: &Timings) -> Duration {
+        match self {
+            Phase::Parse => timings.parse,
+            Phase::Part1 => timings.part1,
+            Phase::Part2 => timings.part2,
+        }
+    }
+}
+
+impl Display for Phase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Part1 => write!(f, "part1"),
+            Phase::Part2 => write!(f, "part2"),
+        }
+    }
+}
+
+impl FromStr for Phase {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Phase, ParseError> {
+        Phase::ALL
+            .into_iter()
+            .find(|phase| phase.to_string() == s)
+            .ok_or_else(|| ParseError::at_column(1, format!("invalid phase '{s}'")))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes a non-empty list of samples.
+    pub fn from_samples(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "cannot summarize zero samples");
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+pub struct Measurement {
+    pub day: u8,
+    pub phase: Phase,
+    pub stats: Stats,
+}
+
+/// Solves `day` `iterations` times and summarizes the timings of every phase.
+pub fn measure(day: &Day, input: &str, iterations: usize) -> Result<Vec<Measurement>, ParseError> {
+    let runs = (0..iterations.max(1))
+        .map(|_| day.solver.time(input))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(Phase::ALL
+        .into_iter()
+        .map(|phase| {
+            let mut samples: Vec<_> = runs.iter().map(|timings| phase.of(timings)).collect();
+            Measurement {
+                day: day.number,
+                phase,
+                stats: Stats::from_samples(&mut samples),
+            }
+        })
+        .collect())
+}
+
+/// How a measurement relates to its baseline.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Comparison {
+    pub baseline: Duration,
+    /// The relative change of the median, e.g. `0.1` for 10% slower.
+    pub change: f64,
+    pub regression: bool,
+}
+
+/// The median times of an earlier benchmark run.
+#[derive(Default, PartialEq, Debug)]
+pub struct Baseline(BTreeMap<(u8, Phase), Duration>);
+
+impl Baseline {
+    pub fn from_measurements(measurements: &[Measurement]) -> Baseline {
+        Baseline(
+            measurements
+                .iter()
+                .map(|m| ((m.day, m.phase), m.stats.median))
+                .collect(),
+        )
+    }
+
+    /// Adds the entries of `other`, replacing the ones for the same day and phase.
+    pub fn merge(&mut self, other: Baseline) {
+        self.0.extend(other.0);
+    }
+
+    /// Compares the median of `measurement` with the baseline. Slowdowns of more than
+    /// `threshold` (relative, e.g. `0.1` for 10%) count as regressions.
+    pub fn compare(&self, measurement: &Measurement, threshold: f64) -> Option<Comparison> {
+        let baseline = *self.0.get(&(measurement.day, measurement.phase))?;
+        let change = if baseline.is_zero() {
+            0.0
+        } else {
+            measurement.stats.median.as_secs_f64() / baseline.as_secs_f64() - 1.0
+        };
+        Some(Comparison {
+            baseline,
+            change,
+            regression: change > threshold,
+        })
+    }
+}
+
+impl FromStr for Baseline {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Baseline, ParseError> {
+        parse_lines(s, |line| {
+            let fields: Vec<&str> = line.split(' ').collect();
+            let [day, phase, nanos] = fields.as_slice() else {
+                return Err(ParseError::at_column(
+                    1,
+                    "expected day, phase and nanoseconds",
+                ));
+            };
+            let day = day
+                .parse()
+                .map_err(|_| ParseError::at_column(1, format!("invalid day '{day}'")))?;
+            let phase = phase.parse().map_err(|err: ParseError| {
+                ParseError::at_column(fields[0].len() + 2, err.message)
+            })?;
+            let nanos = nanos.parse().map_err(|_| {
+                let column = fields[0].len() + fields[1].len() + 3;
+                ParseError::at_column(column, format!("invalid duration '{nanos}'"))
+            })?;
+            Ok(((day, phase), Duration::from_nanos(nanos)))
+        })
+        .map(|entries| Baseline(entries.into_iter().collect()))
+    }
+}
+
+impl Display for Baseline {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for ((day, phase), median) in &self.0 {
+            writeln!(f, "{day:02} {phase} {}", median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(phase: Phase, median: u64) -> Measurement {
+        let median = Duration::from_millis(median);
+        Measurement {
+            day: 12,
+            phase,
+            stats: Stats {
+                min: median,
+                median,
+                max: median,
+            },
+        }
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let mut samples = [5, 1, 4, 2, 3].map(Duration::from_millis);
+        let stats = Stats::from_samples(&mut samples);
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.max, Duration::from_millis(5));
+    }
+
+    #[test]
+    fn baseline_round_trip() {
+        let baseline = Baseline::from_measurements(&[
+            measurement(Phase::Parse, 1),
+            measurement(Phase::Part2, 20),
+        ]);
+        let text = baseline.to_string();
+        assert_eq!(text, "12 parse 1000000\n12 part2 20000000\n");
+        assert_eq!(text.parse::<Baseline>().unwrap(), baseline);
+    }
+
+    #[test]
+    fn invalid_baseline() {
+        let err = "07 parse 100\n08 part3 100\n"
+            .parse::<Baseline>()
+            .err()
+            .unwrap();
+        assert_eq!(err, ParseError::new(2, 4, "invalid phase 'part3'"));
+
+        let err = "07 parse 1.5\n".parse::<Baseline>().err().unwrap();
+        assert_eq!(err, ParseError::new(1, 10, "invalid duration '1.5'"));
+    }
+
+    #[test]
+    fn compare_with_baseline() {
+        let baseline = Baseline::from_measurements(&[measurement(Phase::Part1, 100)]);
+
+        let same = baseline
+            .compare(&measurement(Phase::Part1, 105), 0.1)
+            .unwrap();
+        assert!(!same.regression);
+
+        let slower = baseline
+            .compare(&measurement(Phase::Part1, 150), 0.1)
+            .unwrap();
+        assert!(slower.regression);
+        assert!((slower.change - 0.5).abs() < 1e-9);
+
+        assert_eq!(baseline.compare(&measurement(Phase::Part2, 100), 0.1), None);
+    }
+}
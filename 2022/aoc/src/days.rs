@@ -0,0 +1,70 @@
+use aoc_common::Solver;
+
+pub struct Day {
+    pub number: u8,
+    pub solver: &'static (dyn Solver + Sync),
+    pub input: &'static str,
+    /// Creates a random input from a seed and a size.
+    pub generate: Option<fn(u64, usize) -> String>,
+}
+
+pub static DAYS: &[Day] = &[
+    Day {
+        number: 7,
+        solver: &aoc2207::Day07,
+        input: aoc2207::INPUT,
+        generate: Some(aoc2207::generate::generate),
+    },
+    Day {
+        number: 8,
+        solver: &aoc2208::Day08,
+        input: aoc2208::INPUT,
+        generate: Some(aoc2208::generate::generate),
+    },
+    Day {
+        number: 9,
+        solver: &aoc2209::Day09,
+        input: aoc2209::INPUT,
+        generate: Some(aoc2209::generate::generate),
+    },
+    Day {
+        number: 10,
+        solver: &aoc2210::Day10,
+        input: aoc2210::INPUT,
+        generate: Some(aoc2210::generate::generate),
+    },
+    Day {
+        number: 11,
+        solver: &aoc2211::Day11,
+        input: aoc2211::INPUT,
+        generate: None,
+    },
+    Day {
+        number: 12,
+        solver: &aoc2212::Day12,
+        input: aoc2212::INPUT,
+        generate: Some(aoc2212::generate::generate),
+    },
+    Day {
+        number: 13,
+        solver: &aoc2213::Day13,
+        input: aoc2213::INPUT,
+        generate: Some(aoc2213::generate::generate),
+    },
+    Day {
+        number: 14,
+        solver: &aoc2214::Day14,
+        input: aoc2214::INPUT,
+        generate: Some(aoc2214::generate::generate),
+    },
+    Day {
+        number: 15,
+        solver: &aoc2215::Day15::PUZZLE,
+        input: aoc2215::INPUT,
+        generate: Some(aoc2215::generate::generate),
+    },
+];
+
+pub fn find(number: u8) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
@@ -0,0 +1,135 @@
+use std::process::ExitCode;
+
+use aoc2207::filesystem::Filesystem;
+use aoc2207::planner::Cleanup;
+use aoc2207::{diff, export, report, Day07, INPUT};
+use aoc_common::{InputSource, Solution};
+use clap::Subcommand;
+
+/// The reports about the file system of day 07.
+#[derive(Subcommand)]
+pub enum Report {
+    /// Lists every directory with its total size
+    Du,
+
+    /// Lists the largest directories, largest first
+    Largest {
+        /// How many directories to list
+        #[arg(default_value_t = 10)]
+        count: usize,
+    },
+
+    /// Prints the whole tree with the sizes of all files and directories
+    Tree,
+
+    /// Prints the size of a file or directory
+    Size {
+        /// The absolute path, like `/a/e`
+        path: String,
+    },
+
+    /// Exports the tree as JSON, which can be read back with `--from-json`
+    Json,
+
+    /// Exports the tree as a terminal session that lists every directory once
+    Transcript,
+
+    /// Lists the files and directories that were added, removed or resized in another
+    /// snapshot, including the change in size of every directory
+    Diff {
+        /// The other snapshot (or `-` for stdin), in the same format as the input
+        after: String,
+    },
+
+    /// Plans which directories to delete to free enough space for the update, deleting as
+    /// little as possible
+    Plan {
+        /// The size of the disk
+        #[arg(short, long, default_value_t = Cleanup::PUZZLE.capacity)]
+        capacity: usize,
+
+        /// The free space the update needs
+        #[arg(short, long, default_value_t = Cleanup::PUZZLE.required)]
+        required: usize,
+
+        /// Also consider deleting individual files
+        #[arg(short, long)]
+        files: bool,
+    },
+}
+
+/// Reads a terminal session, or a tree exported as JSON if `from_json` is set.
+fn load(source: &InputSource, from_json: bool) -> Result<Filesystem, String> {
+    let input = source
+        .read(INPUT)
+        .map_err(|err| format!("Cannot read {source}: {err}"))?;
+    let filesystem = if from_json {
+        export::from_json(&input).map_err(|err| err.to_string())
+    } else {
+        Day07.parse(&input).map_err(|err| err.to_string())
+    };
+    filesystem.map_err(|err| format!("Invalid input in {source}: {err}"))
+}
+
+pub fn report(report: &Report, source: &InputSource, from_json: bool, human: bool) -> ExitCode {
+    let filesystem = match load(source, from_json) {
+        Ok(filesystem) => filesystem,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match report {
+        Report::Du => print!("{}", report::du(&filesystem, human)),
+        Report::Largest { count } => print!("{}", report::largest(&filesystem, *count, human)),
+        Report::Tree => print!("{}", report::tree(&filesystem, human)),
+        Report::Json => println!("{}", export::to_json(&filesystem)),
+        Report::Transcript => print!("{}", export::to_transcript(&filesystem)),
+        Report::Size { path } => match filesystem.size_of(path) {
+            Some(size) => println!("{}", report::format_size(size, human)),
+            None => {
+                eprintln!("{path} does not exist");
+                return ExitCode::FAILURE;
+            }
+        },
+        Report::Plan {
+            capacity,
+            required,
+            files,
+        } => {
+            let cleanup = Cleanup {
+                capacity: *capacity,
+                required: *required,
+                files: *files,
+            };
+            let Some(plan) = cleanup.plan(&filesystem) else {
+                eprintln!("Deleting everything does not free enough space");
+                return ExitCode::FAILURE;
+            };
+            let size = |size| report::format_size(size, human);
+            for (deletion, freed) in &plan.deletions {
+                println!("{}\t{}", size(*freed), deletion.path(&filesystem));
+            }
+            println!(
+                "Frees {} of the {} needed, {} more than necessary",
+                size(plan.freed),
+                size(plan.needed),
+                size(plan.over_freed())
+            );
+        }
+        Report::Diff { after } => {
+            let after = match load(&InputSource::from_arg(Some(after)), from_json) {
+                Ok(after) => after,
+                Err(err) => {
+                    eprintln!("{err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+            for change in diff::diff(&filesystem, &after) {
+                println!("{}", change.format(human));
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,196 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use aoc2208::render::{self, Layer};
+use aoc2208::report::{most_scenic, tree_reports};
+use aoc2208::sight::Compass;
+use aoc2208::viewshed::{format_viewshed, viewshed, Observer};
+use aoc2208::{Day08, TreeHeights, INPUT};
+use aoc_common::{InputSource, Solution};
+use clap::{Subcommand, ValueEnum};
+
+/// Reads an altitude, which has to be a finite number for the sight lines to make sense.
+fn parse_altitude(arg: &str) -> Result<f64, String> {
+    let altitude: f64 = arg
+        .parse()
+        .map_err(|err: std::num::ParseFloatError| err.to_string())?;
+    if altitude.is_finite() {
+        Ok(altitude)
+    } else {
+        Err(format!("{arg} is not a finite number"))
+    }
+}
+
+/// The reports about the trees of day 08.
+#[derive(Subcommand)]
+pub enum Report {
+    /// Describes one tree: its height, the directions it can be seen from, its viewing
+    /// distances and its scenic score
+    Tree {
+        /// The row, counted from 0 at the top
+        row: usize,
+
+        /// The column, counted from 0 at the left
+        col: usize,
+    },
+
+    /// Describes every tree, row by row
+    Trees {
+        /// Only list the trees that can be seen from outside the forest
+        #[arg(short, long)]
+        visible: bool,
+    },
+
+    /// Lists the trees with the highest scenic scores, highest first
+    Scenic {
+        /// How many trees to list
+        #[arg(default_value_t = 10)]
+        count: usize,
+    },
+
+    /// Shows which trees can be seen from the top of a tree, or from a point above the forest,
+    /// looking in any direction over the trees in front
+    Viewshed {
+        /// The row of the observer, counted from 0 at the top
+        row: usize,
+
+        /// The column of the observer, counted from 0 at the left
+        col: usize,
+
+        /// Float at this height above the ground instead of standing on the tree
+        #[arg(short, long, value_parser = parse_altitude)]
+        altitude: Option<f64>,
+    },
+
+    /// Draws the forest in colour on the terminal, or as a PPM image
+    Render {
+        /// What the colours show
+        #[arg(short, long, value_enum, default_value_t = Shown::Heights)]
+        layer: Shown,
+
+        /// Write a PPM image to this file instead of printing to the terminal
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// The width and height of a tree in the image, in pixels
+        #[arg(short, long, default_value_t = 4, requires = "output")]
+        scale: usize,
+    },
+}
+
+/// The layers of [`Report::Render`].
+#[derive(Clone, Copy, ValueEnum)]
+pub enum Shown {
+    /// The heights of the trees
+    Heights,
+    /// The trees that can be seen from outside the forest
+    Visibility,
+    /// A heatmap of the scenic scores, with the best tree highlighted
+    Scenic,
+}
+
+impl From<Shown> for Layer {
+    fn from(shown: Shown) -> Layer {
+        match shown {
+            Shown::Heights => Layer::Heights,
+            Shown::Visibility => Layer::Visibility,
+            Shown::Scenic => Layer::Scenic,
+        }
+    }
+}
+
+fn load(source: &InputSource) -> Result<TreeHeights, String> {
+    let input = source
+        .read(INPUT)
+        .map_err(|err| format!("Cannot read {source}: {err}"))?;
+    Day08
+        .parse(&input)
+        .map_err(|err| format!("Invalid input in {source}: {err}"))
+}
+
+pub fn report(report: &Report, source: &InputSource, diagonals: bool) -> ExitCode {
+    let tree_heights = match load(source) {
+        Ok(tree_heights) => tree_heights,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let directions: &[Compass] = if diagonals {
+        &Compass::ALL
+    } else {
+        &Compass::ORTHOGONAL
+    };
+
+    match report {
+        Report::Tree { row, col } => {
+            if tree_heights.get((*row, *col)).is_none() {
+                eprintln!("There is no tree at ({row}, {col})");
+                return ExitCode::FAILURE;
+            }
+            let reports = tree_reports(&tree_heights, directions);
+            println!("{}", reports[(*row, *col)].format((*row, *col)));
+        }
+        Report::Trees { visible } => {
+            for (pos, tree) in tree_reports(&tree_heights, directions).iter() {
+                if tree.is_visible() || !visible {
+                    println!("{}", tree.format(pos));
+                }
+            }
+        }
+        Report::Scenic { count } => {
+            for ((row, col), score) in most_scenic(&tree_heights, directions, *count) {
+                println!("{score}\t({row}, {col})");
+            }
+        }
+        Report::Viewshed { row, col, altitude } => {
+            let pos = (*row, *col);
+            if tree_heights.get(pos).is_none() {
+                eprintln!("There is no tree at ({row}, {col})");
+                return ExitCode::FAILURE;
+            }
+            let observer = match altitude {
+                Some(altitude) => Observer::above(pos, *altitude),
+                None => Observer::on_tree(&tree_heights, pos),
+            };
+            let visible = viewshed(&tree_heights, observer);
+            print!("{}", format_viewshed(&tree_heights, observer, &visible));
+            let count = visible.iter().filter(|(_, &visible)| visible).count();
+            let total = tree_heights.width() * tree_heights.height();
+            println!("{count} of {total} trees can be seen");
+        }
+        Report::Render {
+            layer,
+            output,
+            scale,
+        } => {
+            let colors = render::colors(&tree_heights, (*layer).into(), directions);
+            match output {
+                Some(path) => {
+                    if let Err(err) = fs::write(path, render::ppm(&colors, *scale)) {
+                        eprintln!("Cannot write {}: {err}", path.display());
+                        return ExitCode::FAILURE;
+                    }
+                }
+                None => print!("{}", render::ansi(&tree_heights, &colors)),
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn altitudes() {
+        assert_eq!(parse_altitude("4.5"), Ok(4.5));
+        assert_eq!(parse_altitude("-1"), Ok(-1.0));
+        for arg in ["NaN", "inf", "-infinity", "1e400"] {
+            assert!(parse_altitude(arg).is_err(), "{arg}");
+        }
+        assert!(parse_altitude("high").is_err());
+    }
+}
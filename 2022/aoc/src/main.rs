@@ -0,0 +1,478 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::time::Instant;
+
+use aoc_common::diagnostics::{self, Sink};
+use aoc_common::{InputSource, ParseError, Part};
+use clap::{Parser, Subcommand};
+use serde::Serialize;
+
+use crate::bench::Baseline;
+use crate::days::{Day, DAYS};
+use crate::verify::{Answers, Outcome};
+
+mod bench;
+mod days;
+mod filesystem;
+mod forest;
+mod verify;
+
+/// The answers file that is checked into the repository.
+const ANSWERS_FILE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../answers.txt");
+
+#[derive(Parser)]
+#[command(about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Runs one day or, if no day is given, all days
+    Run {
+        /// The day to run
+        day: Option<u8>,
+
+        /// Only run the given part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+
+        /// Read the puzzle input from this file (or `-` for stdin) instead of using the
+        /// bundled input
+        #[arg(short, long, requires = "day")]
+        input: Option<String>,
+
+        /// Print one JSON object per answer instead of plain text
+        #[arg(short, long)]
+        json: bool,
+
+        /// Show the debug output of the solutions: on stderr for plain text, in the
+        /// `diagnostics` field for JSON
+        #[arg(short, long)]
+        debug: bool,
+    },
+
+    /// Measures how long parsing and solving take for one day or, if no day is given, all
+    /// days (build with `--release` for meaningful numbers)
+    Bench {
+        /// The day to measure
+        day: Option<u8>,
+
+        /// How often each day is run
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+
+        /// Compare the medians with a baseline file written by `--save`
+        #[arg(short, long)]
+        baseline: Option<PathBuf>,
+
+        /// Slowdown in percent above which a phase counts as a regression
+        #[arg(short, long, default_value_t = 10.0)]
+        threshold: f64,
+
+        /// Store the medians in this baseline file, replacing the entries of the measured days
+        #[arg(short, long)]
+        save: Option<PathBuf>,
+    },
+
+    /// Checks the answers of one day or, if no day is given, all days against the answers file
+    Verify {
+        /// The day to check
+        day: Option<u8>,
+
+        /// Check the answers for this input file (or `-` for stdin) instead of the bundled input
+        #[arg(short, long, requires = "day")]
+        input: Option<String>,
+
+        /// The answers file
+        #[arg(short, long, default_value = ANSWERS_FILE)]
+        answers: PathBuf,
+
+        /// Record the current answers where they are missing or differ instead of failing
+        #[arg(short, long)]
+        update: bool,
+    },
+
+    /// Prints a random input for a day; the same seed and size always give the same input
+    Generate {
+        /// The day to generate an input for
+        day: u8,
+
+        /// The seed of the random number generator
+        #[arg(short, long, default_value_t = 0)]
+        seed: u64,
+
+        /// How big the input is, e.g. the number of lines; the exact meaning depends on the day
+        #[arg(short = 'n', long, default_value_t = 20)]
+        size: usize,
+    },
+
+    /// Inspects the file system reconstructed from the terminal session of day 07
+    Fs {
+        #[command(subcommand)]
+        report: filesystem::Report,
+
+        /// Read the terminal session from this file (or `-` for stdin) instead of using the
+        /// bundled input
+        #[arg(short, long, global = true)]
+        input: Option<String>,
+
+        /// The input is a tree exported by `fs json` instead of a terminal session
+        #[arg(short = 'J', long, global = true)]
+        from_json: bool,
+
+        /// Print sizes in powers of 1024, like 2.5K or 14M
+        #[arg(short = 'H', long, global = true)]
+        human_readable: bool,
+    },
+
+    /// Inspects the trees of day 08
+    Forest {
+        #[command(subcommand)]
+        report: forest::Report,
+
+        /// Read the map of the trees from this file (or `-` for stdin) instead of using the
+        /// bundled input
+        #[arg(short, long, global = true)]
+        input: Option<String>,
+
+        /// Also look along the diagonals, for visibility and scenic scores
+        #[arg(short, long, global = true)]
+        diagonals: bool,
+    },
+}
+
+/// An answer as printed by `run --json`.
+#[derive(Serialize)]
+struct AnswerRecord<'a> {
+    day: u8,
+    part: u8,
+    answer: &'a str,
+    /// The time spent parsing the input and solving the part.
+    elapsed_ns: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    diagnostics: Option<Vec<String>>,
+}
+
+fn print_answer(
+    day: &Day,
+    input: &str,
+    part: Part,
+    json: bool,
+    debug: bool,
+) -> Result<(), ParseError> {
+    let start = Instant::now();
+    let answer = day.solver.solve(input, part)?;
+    let elapsed = start.elapsed();
+
+    if json {
+        let record = AnswerRecord {
+            day: day.number,
+            part: part.number(),
+            answer: &answer,
+            elapsed_ns: elapsed.as_nanos(),
+            diagnostics: debug.then(diagnostics::take),
+        };
+        println!(
+            "{}",
+            serde_json::to_string(&record).expect("answers can always be serialized")
+        );
+    } else if answer.contains('\n') {
+        print!("Day {:02} part {}:\n{}", day.number, part, answer);
+    } else {
+        println!("Day {:02} part {}: {}", day.number, part, answer);
+    }
+    Ok(())
+}
+
+fn run(days: &[&Day], part: Option<u8>, source: &InputSource, json: bool, debug: bool) -> ExitCode {
+    let parts = match part {
+        Some(1) => vec![Part::One],
+        Some(2) => vec![Part::Two],
+        _ => vec![Part::One, Part::Two],
+    };
+    if debug {
+        diagnostics::set_sink(if json {
+            Sink::Collect(Vec::new())
+        } else {
+            Sink::Stderr
+        });
+    }
+    for day in days {
+        let input = match source.read(day.input) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("Cannot read {source}: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        for part in &parts {
+            if let Err(err) = print_answer(day, &input, *part, json, debug) {
+                eprintln!("Day {:02}: invalid input in {source}: {err}", day.number);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+/// Looks up the given day or returns all days if there is none.
+fn select_days(day: Option<u8>) -> Option<Vec<&'static Day>> {
+    match day {
+        Some(number) => {
+            let day = days::find(number);
+            if day.is_none() {
+                eprintln!("Day {number} is not implemented");
+            }
+            day.map(|day| vec![day])
+        }
+        None => Some(DAYS.iter().collect()),
+    }
+}
+
+/// Reads and parses a file, falling back to the default value if `or_default` is set and the
+/// file does not exist yet.
+fn load<T>(path: &Path, or_default: bool) -> Result<T, String>
+where
+    T: FromStr<Err = ParseError> + Default,
+{
+    if or_default && !path.exists() {
+        return Ok(T::default());
+    }
+    let text =
+        fs::read_to_string(path).map_err(|err| format!("Cannot read {}: {err}", path.display()))?;
+    text.parse()
+        .map_err(|err| format!("Invalid content in {}: {err}", path.display()))
+}
+
+fn bench(
+    days: &[&Day],
+    iterations: usize,
+    baseline: Option<&Path>,
+    threshold: f64,
+    save: Option<&Path>,
+) -> ExitCode {
+    let baseline = match baseline
+        .map(|path| load::<Baseline>(path, false))
+        .transpose()
+    {
+        Ok(baseline) => baseline,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut measurements = Vec::new();
+    let mut regressions = 0;
+    for day in days {
+        let day_measurements = match bench::measure(day, day.input, iterations) {
+            Ok(measurements) => measurements,
+            Err(err) => {
+                eprintln!("Day {:02}: invalid input: {err}", day.number);
+                return ExitCode::FAILURE;
+            }
+        };
+        for m in &day_measurements {
+            print!(
+                "Day {:02} {}: min {:.2?}, median {:.2?}, max {:.2?}",
+                m.day, m.phase, m.stats.min, m.stats.median, m.stats.max
+            );
+            match baseline
+                .as_ref()
+                .and_then(|baseline| baseline.compare(m, threshold / 100.0))
+            {
+                Some(cmp) => {
+                    print!(
+                        " (baseline {:.2?}, {:+.1}%)",
+                        cmp.baseline,
+                        cmp.change * 100.0
+                    );
+                    if cmp.regression {
+                        regressions += 1;
+                        print!(" REGRESSION");
+                    }
+                    println!();
+                }
+                None => println!(),
+            }
+        }
+        measurements.extend(day_measurements);
+    }
+
+    if let Some(path) = save {
+        // keep the entries of the days that were not measured this time
+        let mut stored = match load::<Baseline>(path, true) {
+            Ok(stored) => stored,
+            Err(err) => {
+                eprintln!("{err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        stored.merge(Baseline::from_measurements(&measurements));
+        if let Err(err) = fs::write(path, stored.to_string()) {
+            eprintln!("Cannot write {}: {err}", path.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if regressions > 0 {
+        eprintln!("{regressions} regression(s) found");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn verify(days: &[&Day], source: &InputSource, answers_file: &Path, update: bool) -> ExitCode {
+    let mut answers = match load::<Answers>(answers_file, update) {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut failures = 0;
+    for day in days {
+        let input = match source.read(day.input) {
+            Ok(input) => input,
+            Err(err) => {
+                eprintln!("Cannot read {source}: {err}");
+                return ExitCode::FAILURE;
+            }
+        };
+        for part in [Part::One, Part::Two] {
+            let answer = match day.solver.solve(&input, part) {
+                Ok(answer) => answer,
+                Err(err) => {
+                    eprintln!("Day {:02}: invalid input in {source}: {err}", day.number);
+                    return ExitCode::FAILURE;
+                }
+            };
+            let outcome = answers.check(day.number, part, &input, &answer);
+            print!("Day {:02} part {}: ", day.number, part);
+            match &outcome {
+                Outcome::Correct => println!("ok"),
+                Outcome::Wrong { expected } => println!(
+                    "expected {}, got {}",
+                    verify::escape(expected),
+                    verify::escape(&answer)
+                ),
+                Outcome::Unknown => println!("no answer recorded for this input"),
+            }
+            if outcome != Outcome::Correct {
+                if update {
+                    answers.record(day.number, part, &input, &answer);
+                } else {
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    if update {
+        if let Err(err) = fs::write(answers_file, answers.to_string()) {
+            eprintln!("Cannot write {}: {err}", answers_file.display());
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if failures > 0 {
+        eprintln!("{failures} answer(s) could not be verified");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    match cli.command {
+        Command::Run {
+            day,
+            part,
+            input,
+            json,
+            debug,
+        } => match select_days(day) {
+            Some(days) => run(
+                &days,
+                part,
+                &InputSource::from_arg(input.as_deref()),
+                json,
+                debug,
+            ),
+            None => ExitCode::FAILURE,
+        },
+
+        Command::Bench {
+            day,
+            iterations,
+            baseline,
+            threshold,
+            save,
+        } => match select_days(day) {
+            Some(days) => bench(
+                &days,
+                iterations,
+                baseline.as_deref(),
+                threshold,
+                save.as_deref(),
+            ),
+            None => ExitCode::FAILURE,
+        },
+
+        Command::Verify {
+            day,
+            input,
+            answers,
+            update,
+        } => match select_days(day) {
+            Some(days) => verify(
+                &days,
+                &InputSource::from_arg(input.as_deref()),
+                &answers,
+                update,
+            ),
+            None => ExitCode::FAILURE,
+        },
+
+        Command::Generate { day, seed, size } => {
+            match select_days(Some(day)).map(|days| days[0].generate) {
+                Some(Some(generate)) => {
+                    print!("{}", generate(seed, size));
+                    ExitCode::SUCCESS
+                }
+                Some(None) => {
+                    eprintln!("Day {day} has no input generator");
+                    ExitCode::FAILURE
+                }
+                None => ExitCode::FAILURE,
+            }
+        }
+
+        Command::Fs {
+            report,
+            input,
+            from_json,
+            human_readable,
+        } => {
+            let source = InputSource::from_arg(input.as_deref());
+            filesystem::report(&report, &source, from_json, human_readable)
+        }
+
+        Command::Forest {
+            report,
+            input,
+            diagonals,
+        } => {
+            let source = InputSource::from_arg(input.as_deref());
+            forest::report(&report, &source, diagonals)
+        }
+    }
+}
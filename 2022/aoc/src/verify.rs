@@ -0,0 +1,196 @@
+//! The database of known answers that guards the solutions against regressions.
+//!
+//! Every line of the answers file holds the day, the part, the SHA-256 hash of the input and
+//! the expected answer, e.g. `07 1 3f2a...9c 1423358`. Answers spanning several lines are
+//! stored with escaped line breaks.
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use aoc_common::{parse_lines, ParseError, Part};
+use sha2::{Digest, Sha256};
+
+/// The hex encoded SHA-256 hash of a puzzle input.
+pub fn input_hash(input: &str) -> String {
+    Sha256::digest(input.as_bytes())
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect()
+}
+
+/// Puts `answer` on a single line.
+pub fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(answer: &str) -> Result<String, ParseError> {
+    let mut res = String::new();
+    let mut chars = answer.chars().enumerate();
+    while let Some((idx, c)) = chars.next() {
+        if c != '\\' {
+            res.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some((_, 'n')) => res.push('\n'),
+            Some((_, '\\')) => res.push('\\'),
+            _ => return Err(ParseError::at_column(idx + 1, "invalid escape sequence")),
+        }
+    }
+    Ok(res)
+}
+
+/// What running a day revealed about its recorded answer.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Outcome {
+    Correct,
+    Wrong {
+        expected: String,
+    },
+    /// There is no answer for this day, part and input.
+    Unknown,
+}
+
+#[derive(Default, PartialEq, Debug)]
+pub struct Answers(BTreeMap<(u8, u8, String), String>);
+
+impl Answers {
+    pub fn check(&self, day: u8, part: Part, input: &str, answer: &str) -> Outcome {
+        match self.0.get(&(day, part.number(), input_hash(input))) {
+            Some(expected) if expected == answer => Outcome::Correct,
+            Some(expected) => Outcome::Wrong {
+                expected: expected.clone(),
+            },
+            None => Outcome::Unknown,
+        }
+    }
+
+    pub fn record(&mut self, day: u8, part: Part, input: &str, answer: &str) {
+        self.0
+            .insert((day, part.number(), input_hash(input)), answer.to_string());
+    }
+}
+
+impl FromStr for Answers {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Answers, ParseError> {
+        parse_lines(s, |line| {
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let [day, part, hash, answer] = fields.as_slice() else {
+                return Err(ParseError::at_column(
+                    1,
+                    "expected day, part, input hash and answer",
+                ));
+            };
+            let day = day
+                .parse()
+                .map_err(|_| ParseError::at_column(1, format!("invalid day '{day}'")))?;
+            let part_column = fields[0].len() + 2;
+            let part = match *part {
+                "1" | "2" => part.parse().unwrap(),
+                _ => {
+                    return Err(ParseError::at_column(
+                        part_column,
+                        format!("invalid part '{part}'"),
+                    ))
+                }
+            };
+            let answer_column = part_column + fields[1].len() + fields[2].len() + 2;
+            let answer = unescape(answer).map_err(|err| {
+                ParseError::at_column(answer_column + err.column - 1, err.message)
+            })?;
+            Ok(((day, part, hash.to_string()), answer))
+        })
+        .map(|entries| Answers(entries.into_iter().collect()))
+    }
+}
+
+impl Display for Answers {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for ((day, part, hash), answer) in &self.0 {
+            writeln!(f, "{day:02} {part} {hash} {}", escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_of_input() {
+        assert_eq!(
+            input_hash("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn check_answers() {
+        let mut answers = Answers::default();
+        answers.record(7, Part::One, "input", "42");
+        assert_eq!(answers.check(7, Part::One, "input", "42"), Outcome::Correct);
+        assert_eq!(
+            answers.check(7, Part::One, "input", "43"),
+            Outcome::Wrong {
+                expected: "42".to_string()
+            }
+        );
+        assert_eq!(answers.check(7, Part::Two, "input", "42"), Outcome::Unknown);
+        assert_eq!(
+            answers.check(7, Part::One, "other input", "42"),
+            Outcome::Unknown
+        );
+    }
+
+    #[test]
+    fn answers_round_trip() {
+        let mut answers = Answers::default();
+        answers.record(10, Part::Two, "input", "#..#\n.##.\n");
+        answers.record(9, Part::One, "input", "a\\b c");
+        let text = answers.to_string();
+        assert_eq!(text.lines().count(), 2);
+        assert!(text.ends_with(" #..#\\n.##.\\n\n"));
+        assert_eq!(text.parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    #[ignore = "solving every day takes minutes in debug builds"]
+    fn bundled_answers_are_correct() {
+        let answers: Answers = include_str!("../../answers.txt").parse().unwrap();
+        for day in crate::days::DAYS {
+            for part in [Part::One, Part::Two] {
+                let answer = day.solver.solve(day.input, part).unwrap();
+                let outcome = answers.check(day.number, part, day.input, &answer);
+                assert_eq!(
+                    outcome,
+                    Outcome::Correct,
+                    "day {} part {}",
+                    day.number,
+                    part
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_answers() {
+        let err = "07 3 abc 42\n".parse::<Answers>().err().unwrap();
+        assert_eq!(err, ParseError::new(1, 4, "invalid part '3'"));
+
+        let err = "07 1 abc 42\n07 2 abc 4\\t2\n"
+            .parse::<Answers>()
+            .err()
+            .unwrap();
+        assert_eq!(err, ParseError::new(2, 11, "invalid escape sequence"));
+
+        let err = "07 1 abc\n".parse::<Answers>().err().unwrap();
+        assert_eq!(
+            err,
+            ParseError::new(1, 1, "expected day, part, input hash and answer")
+        );
+    }
+}
@@ -0,0 +1,78 @@
+//! An opt-in channel for debug output of the solutions, kept apart from the answers.
+//!
+//! Solutions report what they want to show with [`diagnostic!`](crate::diagnostic). Messages
+//! are only formatted if a sink is active, so expensive dumps cost nothing by default.
+
+use std::cell::RefCell;
+use std::mem;
+
+/// Where diagnostics go.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub enum Sink {
+    /// Diagnostics are dropped.
+    #[default]
+    Off,
+    Stderr,
+    /// Diagnostics are kept until they are taken with [`take`].
+    Collect(Vec<String>),
+}
+
+thread_local! {
+    static SINK: RefCell<Sink> = const { RefCell::new(Sink::Off) };
+}
+
+/// Sets the sink of the current thread, returning the previous one.
+pub fn set_sink(sink: Sink) -> Sink {
+    SINK.with(|current| mem::replace(&mut *current.borrow_mut(), sink))
+}
+
+/// Removes the messages collected so far, if diagnostics are being collected.
+pub fn take() -> Vec<String> {
+    SINK.with(|sink| match &mut *sink.borrow_mut() {
+        Sink::Collect(messages) => mem::take(messages),
+        _ => Vec::new(),
+    })
+}
+
+/// Hands a message to the current sink; `message` is only called if the sink is active.
+pub fn emit(message: impl FnOnce() -> String) {
+    SINK.with(|sink| match &mut *sink.borrow_mut() {
+        Sink::Off => {}
+        Sink::Stderr => eprintln!("{}", message()),
+        Sink::Collect(messages) => messages.push(message()),
+    })
+}
+
+/// Emits a diagnostic message, with the same arguments as `format!`.
+#[macro_export]
+macro_rules! diagnostic {
+    ($($arg:tt)*) => {
+        $crate::diagnostics::emit(|| format!($($arg)*))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diagnostics_are_off_by_default() {
+        let mut formatted = false;
+        emit(|| {
+            formatted = true;
+            "message".to_string()
+        });
+        assert!(!formatted);
+        assert!(take().is_empty());
+    }
+
+    #[test]
+    fn collect_diagnostics() {
+        set_sink(Sink::Collect(Vec::new()));
+        crate::diagnostic!("found {}", 42);
+        crate::diagnostic!("done");
+        assert_eq!(take(), ["found 42", "done"]);
+        assert!(take().is_empty());
+        assert_eq!(set_sink(Sink::Off), Sink::Collect(Vec::new()));
+    }
+}
@@ -0,0 +1,122 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+/// An error in the puzzle input, located by its 1-based line and column.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> ParseError {
+        ParseError {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an error for a single line of text, i.e. one that is located in line 1.
+    pub fn at_column(column: usize, message: impl Into<String>) -> ParseError {
+        ParseError::new(1, column, message)
+    }
+
+    /// Creates an error at the given byte offset into `input`.
+    pub fn at_offset(input: &str, offset: usize, message: impl Into<String>) -> ParseError {
+        let before = &input[..offset.min(input.len())];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        let column = before[line_start..].chars().count() + 1;
+        ParseError::new(line, column, message)
+    }
+
+    /// Moves the error down by `lines`, used when a piece of the input was parsed on its own.
+    pub fn offset_lines(self, lines: usize) -> ParseError {
+        ParseError {
+            line: self.line + lines,
+            ..self
+        }
+    }
+
+    /// Creates an error from what is left over of `input` after a failed nom parser.
+    #[cfg(feature = "nom")]
+    pub fn from_nom(input: &str, err: nom::Err<nom::error::Error<&str>>) -> ParseError {
+        match err {
+            nom::Err::Error(err) | nom::Err::Failure(err) => ParseError::at_offset(
+                input,
+                input.len() - err.input.len(),
+                format!("unexpected input ({})", err.code.description()),
+            ),
+            nom::Err::Incomplete(_) => {
+                ParseError::at_offset(input, input.len(), "unexpected end of input")
+            }
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses every non-empty line of `input` with `parse_line`, reporting errors at their line.
+pub fn parse_lines<T, F>(input: &str, parse_line: F) -> Result<Vec<T>, ParseError>
+where
+    F: Fn(&str) -> Result<T, ParseError>,
+{
+    input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(idx, line)| parse_line(line).map_err(|err| err.offset_lines(idx)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_at_offset() {
+        let input = "abc\ndef\nghi";
+        assert_eq!(
+            ParseError::at_offset(input, 0, "x"),
+            ParseError::new(1, 1, "x")
+        );
+        assert_eq!(
+            ParseError::at_offset(input, 5, "x"),
+            ParseError::new(2, 2, "x")
+        );
+        assert_eq!(
+            ParseError::at_offset(input, 11, "x"),
+            ParseError::new(3, 4, "x")
+        );
+    }
+
+    #[test]
+    fn parse_lines_reports_line_numbers() {
+        let res = parse_lines("1\n2\n\nx\n", |line| {
+            line.parse::<u8>()
+                .map_err(|err| ParseError::at_column(1, err.to_string()))
+        });
+        assert_eq!(
+            res,
+            Err(ParseError::new(4, 1, "invalid digit found in string"))
+        );
+    }
+
+    #[test]
+    fn display() {
+        let err = ParseError::new(3, 7, "invalid direction 'X'");
+        assert_eq!(err.to_string(), "line 3, column 7: invalid direction 'X'");
+    }
+}
@@ -0,0 +1,234 @@
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::hint::black_box;
+use std::io::Read;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+use std::{env, fs, io};
+
+pub use crate::error::{parse_lines, ParseError};
+
+pub mod diagnostics;
+mod error;
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The interface every day implements.
+///
+/// The puzzle input is parsed once and then handed to both parts, so tooling can measure
+/// or check parsing and solving separately.
+pub trait Solution {
+    type Input;
+    type Answer1: Answer;
+    type Answer2: Answer;
+
+    fn parse(&self, input: &str) -> Result<Self::Input, ParseError>;
+    fn part1(&self, input: &Self::Input) -> Self::Answer1;
+    fn part2(&self, input: &Self::Input) -> Self::Answer2;
+}
+
+/// The answer to one part of a puzzle. A part that finds the input has no answer returns
+/// `Result<T, ParseError>` instead of `T`.
+pub trait Answer {
+    fn render(self) -> Result<String, ParseError>;
+}
+
+macro_rules! display_answer {
+    ($($ty:ty),*) => {
+        $(impl Answer for $ty {
+            fn render(self) -> Result<String, ParseError> {
+                Ok(self.to_string())
+            }
+        })*
+    };
+}
+
+display_answer!(usize, isize, u64, String);
+
+impl<T: Answer> Answer for Result<T, ParseError> {
+    fn render(self) -> Result<String, ParseError> {
+        self?.render()
+    }
+}
+
+/// The time spent in each phase of a single run of a [`Solution`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Timings {
+    pub parse: Duration,
+    pub part1: Duration,
+    pub part2: Duration,
+}
+
+/// Object safe view of a [`Solution`] with answers rendered as strings.
+pub trait Solver {
+    fn solve(&self, input: &str, part: Part) -> Result<String, ParseError>;
+
+    /// Parses `input` and solves both parts once, measuring each phase on its own.
+    fn time(&self, input: &str) -> Result<Timings, ParseError>;
+}
+
+impl<S: Solution> Solver for S {
+    fn solve(&self, input: &str, part: Part) -> Result<String, ParseError> {
+        let input = self.parse(input)?;
+        match part {
+            Part::One => self.part1(&input).render(),
+            Part::Two => self.part2(&input).render(),
+        }
+    }
+
+    fn time(&self, input: &str) -> Result<Timings, ParseError> {
+        let start = Instant::now();
+        let input = black_box(self.parse(input)?);
+        let parse = start.elapsed();
+
+        let start = Instant::now();
+        black_box(self.part1(&input));
+        let part1 = start.elapsed();
+
+        let start = Instant::now();
+        black_box(self.part2(&input));
+        let part2 = start.elapsed();
+
+        Ok(Timings {
+            parse,
+            part1,
+            part2,
+        })
+    }
+}
+
+/// Where the puzzle input is read from.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum InputSource {
+    /// The input compiled into the binary.
+    Bundled,
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Interprets a command line argument: `-` selects stdin, anything else is a file path.
+    pub fn from_arg(arg: Option<&str>) -> InputSource {
+        match arg {
+            None => InputSource::Bundled,
+            Some("-") => InputSource::Stdin,
+            Some(path) => InputSource::File(path.into()),
+        }
+    }
+
+    pub fn read(&self, bundled: &'static str) -> io::Result<Cow<'static, str>> {
+        match self {
+            InputSource::Bundled => Ok(Cow::Borrowed(bundled)),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(Cow::Owned(input))
+            }
+            InputSource::File(path) => Ok(Cow::Owned(fs::read_to_string(path)?)),
+        }
+    }
+}
+
+impl Display for InputSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputSource::Bundled => write!(f, "bundled input"),
+            InputSource::Stdin => write!(f, "stdin"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// The `main` of every day: reads the input named by the first command line argument
+/// (falling back to the bundled input) and prints both answers. Diagnostics are printed to
+/// stderr if the `AOC_DEBUG` environment variable is set.
+pub fn main<S: Solution>(solution: &S, bundled: &'static str) -> ExitCode {
+    if env::var_os("AOC_DEBUG").is_some() {
+        diagnostics::set_sink(diagnostics::Sink::Stderr);
+    }
+    let source = InputSource::from_arg(env::args().nth(1).as_deref());
+    let input = match source.read(bundled) {
+        Ok(input) => input,
+        Err(err) => {
+            eprintln!("Cannot read {source}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match run(solution, &input) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("Invalid input in {source}: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Solves both parts and prints the answers.
+pub fn run<S: Solution>(solution: &S, input: &str) -> Result<(), ParseError> {
+    let input = solution.parse(input)?;
+    print_answer(Part::One, solution.part1(&input).render()?);
+    print_answer(Part::Two, solution.part2(&input).render()?);
+    Ok(())
+}
+
+fn print_answer(part: Part, answer: String) {
+    if answer.contains('\n') {
+        print!("Answer {part}:\n{answer}");
+    } else {
+        println!("Answer {part}: {answer}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn input_source_from_arg() {
+        assert_eq!(InputSource::from_arg(None), InputSource::Bundled);
+        assert_eq!(InputSource::from_arg(Some("-")), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg(Some("input.txt")),
+            InputSource::File("input.txt".into())
+        );
+    }
+
+    #[test]
+    fn bundled_input_is_borrowed() {
+        let input = InputSource::Bundled.read("1\n2\n").unwrap();
+        assert!(matches!(input, Cow::Borrowed("1\n2\n")));
+    }
+
+    #[test]
+    fn failed_answers_are_errors() {
+        assert_eq!(42usize.render(), Ok("42".to_string()));
+        let err = ParseError::new(3, 1, "no answer");
+        assert_eq!(Err::<u64, _>(err.clone()).render(), Err(err));
+    }
+
+    #[test]
+    fn missing_file_is_an_error() {
+        let source = InputSource::File("does/not/exist.txt".into());
+        assert!(source.read("").is_err());
+    }
+}
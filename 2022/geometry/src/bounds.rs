@@ -0,0 +1,276 @@
+use crate::{Coord, Point};
+
+/// The smallest axis-aligned box containing a set of points, with inclusive limits.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Bounds<T> {
+    Empty,
+    Bounded { xmin: T, xmax: T, ymin: T, ymax: T },
+}
+
+impl<T: Coord> Bounds<T> {
+    pub fn xmin(&self) -> Option<T> {
+        if let Bounds::Bounded { xmin, .. } = self {
+            Some(*xmin)
+        } else {
+            None
+        }
+    }
+
+    pub fn xmax(&self) -> Option<T> {
+        if let Bounds::Bounded { xmax, .. } = self {
+            Some(*xmax)
+        } else {
+            None
+        }
+    }
+
+    pub fn ymin(&self) -> Option<T> {
+        if let Bounds::Bounded { ymin, .. } = self {
+            Some(*ymin)
+        } else {
+            None
+        }
+    }
+
+    pub fn ymax(&self) -> Option<T> {
+        if let Bounds::Bounded { ymax, .. } = self {
+            Some(*ymax)
+        } else {
+            None
+        }
+    }
+
+    /// The top left corner.
+    pub fn min(&self) -> Option<Point<T>> {
+        Some(Point::new(self.xmin()?, self.ymin()?))
+    }
+
+    /// The bottom right corner.
+    pub fn max(&self) -> Option<Point<T>> {
+        Some(Point::new(self.xmax()?, self.ymax()?))
+    }
+
+    pub fn union(&self, rhs: &Bounds<T>) -> Self {
+        match (self, rhs) {
+            (Bounds::Empty, Bounds::Empty) => Bounds::Empty,
+            (lhs, Bounds::Empty) => *lhs,
+            (Bounds::Empty, rhs) => *rhs,
+            (
+                Bounds::Bounded {
+                    xmin: xminl,
+                    xmax: xmaxl,
+                    ymin: yminl,
+                    ymax: ymaxl,
+                },
+                Bounds::Bounded {
+                    xmin: xminr,
+                    xmax: xmaxr,
+                    ymin: yminr,
+                    ymax: ymaxr,
+                },
+            ) => Bounds::Bounded {
+                xmin: *xminl.min(xminr),
+                xmax: *xmaxl.max(xmaxr),
+                ymin: *yminl.min(yminr),
+                ymax: *ymaxl.max(ymaxr),
+            },
+        }
+    }
+
+    pub fn contains(&self, point: Point<T>) -> bool {
+        match self {
+            Bounds::Empty => false,
+            Bounds::Bounded {
+                xmin,
+                xmax,
+                ymin,
+                ymax,
+            } => (*xmin..=*xmax).contains(&point.x) && (*ymin..=*ymax).contains(&point.y),
+        }
+    }
+
+    pub fn to_tuple(self) -> Option<(T, T, T, T)> {
+        match self {
+            Bounds::Empty => None,
+            Bounds::Bounded {
+                xmin,
+                xmax,
+                ymin,
+                ymax,
+            } => Some((xmin, xmax, ymin, ymax)),
+        }
+    }
+
+    pub fn delta_x(&self) -> Option<T> {
+        match self {
+            Bounds::Empty => None,
+            Bounds::Bounded { xmin, xmax, .. } => Some(*xmax - *xmin),
+        }
+    }
+
+    pub fn delta_y(&self) -> Option<T> {
+        match self {
+            Bounds::Empty => None,
+            Bounds::Bounded { ymin, ymax, .. } => Some(*ymax - *ymin),
+        }
+    }
+
+    /// The number of columns covered by the box.
+    pub fn width(&self) -> T {
+        self.delta_x().map_or(T::ZERO, |dx| dx + T::ONE)
+    }
+
+    /// The number of rows covered by the box.
+    pub fn height(&self) -> T {
+        self.delta_y().map_or(T::ZERO, |dy| dy + T::ONE)
+    }
+}
+
+/// Something that occupies a region of the plane.
+pub trait Bounded<T: Coord> {
+    fn bounds(&self) -> Bounds<T>;
+}
+
+impl<T: Coord> Bounded<T> for Point<T> {
+    fn bounds(&self) -> Bounds<T> {
+        Bounds::Bounded {
+            xmin: self.x,
+            xmax: self.x,
+            ymin: self.y,
+            ymax: self.y,
+        }
+    }
+}
+
+impl<T, E> Bounded<T> for [E]
+where
+    T: Coord,
+    E: Bounded<T>,
+{
+    fn bounds(&self) -> Bounds<T> {
+        self.iter()
+            .fold(Bounds::Empty, |bounds, b| bounds.union(&b.bounds()))
+    }
+}
+
+impl<T, E> Bounded<T> for Vec<E>
+where
+    T: Coord,
+    E: Bounded<T>,
+{
+    fn bounds(&self) -> Bounds<T> {
+        self.as_slice().bounds()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_union_bounded_bounded() {
+        let b1 = Bounds::Bounded {
+            xmin: 1,
+            xmax: 4,
+            ymin: 2,
+            ymax: 10,
+        };
+        let b2 = Bounds::Bounded {
+            xmin: 10,
+            xmax: 40,
+            ymin: 20,
+            ymax: 100,
+        };
+        assert_eq!(
+            b1.union(&b2),
+            Bounds::Bounded {
+                xmin: 1,
+                xmax: 40,
+                ymin: 2,
+                ymax: 100
+            }
+        );
+    }
+
+    #[test]
+    fn bounds_union_empty_bounded() {
+        let b1 = Bounds::Empty;
+        let b2 = Bounds::Bounded {
+            xmin: 10,
+            xmax: 40,
+            ymin: 20,
+            ymax: 100,
+        };
+        assert_eq!(b1.union(&b2), b2);
+    }
+
+    #[test]
+    fn bounds_union_bounded_empty() {
+        let b1 = Bounds::Bounded {
+            xmin: 1,
+            xmax: 4,
+            ymin: 2,
+            ymax: 10,
+        };
+        let b2 = Bounds::Empty;
+        assert_eq!(b1.union(&b2), b1);
+    }
+
+    #[test]
+    fn bounds_to_tuple_empty() {
+        let b = Bounds::<usize>::Empty;
+        assert_eq!(b.to_tuple(), None);
+    }
+
+    #[test]
+    fn bounds_to_tuple_bounded() {
+        let b = Bounds::Bounded {
+            xmin: 1,
+            xmax: 2,
+            ymin: 3,
+            ymax: 4,
+        };
+        assert_eq!(b.to_tuple(), Some((1, 2, 3, 4)));
+    }
+
+    #[test]
+    fn bounds_delta_x_y_empty() {
+        let b = Bounds::<usize>::Empty;
+        assert_eq!(b.delta_x(), None);
+        assert_eq!(b.delta_y(), None);
+        assert_eq!(b.width(), 0);
+        assert_eq!(b.height(), 0);
+    }
+
+    #[test]
+    fn bounds_delta_x_y_bounded() {
+        let b = Bounds::Bounded {
+            xmin: 1,
+            xmax: 2,
+            ymin: 3,
+            ymax: 4,
+        };
+        assert_eq!(b.delta_x(), Some(1));
+        assert_eq!(b.delta_y(), Some(1));
+        assert_eq!(b.width(), 2);
+        assert_eq!(b.height(), 2);
+    }
+
+    #[test]
+    fn points_bounds() {
+        let points = vec![Point::new(-3, 5), Point::new(2, -1), Point::new(0, 0)];
+        let bounds = points.bounds();
+        assert_eq!(
+            bounds,
+            Bounds::Bounded {
+                xmin: -3,
+                xmax: 2,
+                ymin: -1,
+                ymax: 5
+            }
+        );
+        assert_eq!(bounds.min(), Some(Point::new(-3, -1)));
+        assert!(bounds.contains(Point::new(2, 5)));
+        assert!(!bounds.contains(Point::new(3, 0)));
+    }
+}
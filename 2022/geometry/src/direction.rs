@@ -0,0 +1,81 @@
+use std::fmt::{Display, Formatter};
+
+use crate::{Point, SignedCoord};
+
+/// One of the four directions parallel to the axes.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions in clockwise order, starting with `Up`.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The vector of length one pointing into this direction.
+    pub fn offset<T: SignedCoord>(self) -> Point<T> {
+        match self {
+            Direction::Up => Point::new(T::ZERO, -T::ONE),
+            Direction::Right => Point::new(T::ONE, T::ZERO),
+            Direction::Down => Point::new(T::ZERO, T::ONE),
+            Direction::Left => Point::new(-T::ONE, T::ZERO),
+        }
+    }
+
+    pub fn turn_right(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    pub fn turn_left(self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(self) -> Direction {
+        self.turn_right().turn_right()
+    }
+}
+
+impl Display for Direction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Direction::Up => write!(f, "^"),
+            Direction::Right => write!(f, ">"),
+            Direction::Down => write!(f, "v"),
+            Direction::Left => write!(f, "<"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offsets_use_screen_coordinates() {
+        assert_eq!(Direction::Up.offset(), Point::new(0, -1));
+        assert_eq!(Direction::Down.offset(), Point::new(0, 1));
+        assert_eq!(Direction::Left.offset(), Point::new(-1, 0));
+        assert_eq!(Direction::Right.offset(), Point::new(1, 0));
+    }
+
+    #[test]
+    fn turns() {
+        for direction in Direction::ALL {
+            assert_eq!(direction.turn_left().turn_right(), direction);
+            assert_eq!(direction.opposite().offset::<i32>(), -direction.offset());
+        }
+    }
+}
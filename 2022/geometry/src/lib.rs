@@ -0,0 +1,58 @@
+//! Points, directions and bounding boxes on the integer plane.
+//!
+//! All days use screen coordinates: `x` grows to the right and `y` grows downwards.
+
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, Mul, Neg, Sub};
+
+pub use crate::bounds::{Bounded, Bounds};
+pub use crate::direction::Direction;
+pub use crate::point::Point;
+
+mod bounds;
+mod direction;
+mod point;
+
+/// An integer type that can be used as a coordinate.
+pub trait Coord:
+    Copy + Ord + Hash + Debug + Add<Output = Self> + Sub<Output = Self> + Mul<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+/// A coordinate type that can be negative, which is required for offsets.
+pub trait SignedCoord: Coord + Neg<Output = Self> {
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_coord {
+    ($($t:ty)*) => {$(
+        impl Coord for $t {
+            const ZERO: $t = 0;
+            const ONE: $t = 1;
+        }
+    )*};
+}
+
+macro_rules! impl_signed_coord {
+    ($($t:ty)*) => {$(
+        impl SignedCoord for $t {
+            fn signum(self) -> $t {
+                <$t>::signum(self)
+            }
+        }
+    )*};
+}
+
+impl_coord!(i8 i16 i32 i64 isize u8 u16 u32 u64 usize);
+impl_signed_coord!(i8 i16 i32 i64 isize);
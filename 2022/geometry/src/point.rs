@@ -0,0 +1,193 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::{Coord, Direction, SignedCoord};
+
+/// A point on the plane, which doubles as the vector between two points.
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Point<T> {
+        Point { x, y }
+    }
+}
+
+impl<T: Coord> Point<T> {
+    pub const ORIGIN: Point<T> = Point::new(T::ZERO, T::ZERO);
+
+    pub fn manhattan_distance(self, other: Point<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    /// The number of king moves needed to get from `self` to `other`.
+    pub fn chebyshev_distance(self, other: Point<T>) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+
+    /// Converts both coordinates to another integer type.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Result<Point<U>, U::Error> {
+        Ok(Point::new(U::try_from(self.x)?, U::try_from(self.y)?))
+    }
+}
+
+impl<T: SignedCoord> Point<T> {
+    /// The vector whose coordinates are the signs of this vector's coordinates, i.e. the
+    /// single step that goes (diagonally, if necessary) into the direction of `self`.
+    pub fn signum(self) -> Point<T> {
+        Point::new(self.x.signum(), self.y.signum())
+    }
+
+    pub fn step(self, direction: Direction) -> Point<T> {
+        self + direction.offset()
+    }
+
+    /// The four horizontally and vertically adjacent points.
+    pub fn neighbors4(self) -> impl Iterator<Item = Point<T>> {
+        Direction::ALL
+            .into_iter()
+            .map(move |direction| self.step(direction))
+    }
+
+    /// All eight adjacent points, including the diagonal ones.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point<T>> {
+        Direction::ALL.into_iter().flat_map(move |direction| {
+            let next = self.step(direction);
+            [next, next.step(direction.turn_right())]
+        })
+    }
+}
+
+impl Point<usize> {
+    /// Adds a signed offset, returning `None` if the result would leave the first quadrant.
+    pub fn checked_add_signed(self, delta: Point<isize>) -> Option<Point<usize>> {
+        Some(Point::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+        ))
+    }
+}
+
+impl<T: Coord> Add for Point<T> {
+    type Output = Point<T>;
+
+    fn add(self, rhs: Point<T>) -> Point<T> {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: Coord> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Point<T>) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: Coord> Sub for Point<T> {
+    type Output = Point<T>;
+
+    fn sub(self, rhs: Point<T>) -> Point<T> {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Coord> SubAssign for Point<T> {
+    fn sub_assign(&mut self, rhs: Point<T>) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: Coord> Mul<T> for Point<T> {
+    type Output = Point<T>;
+
+    fn mul(self, rhs: T) -> Point<T> {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: SignedCoord> Neg for Point<T> {
+    type Output = Point<T>;
+
+    fn neg(self) -> Point<T> {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Point<T> {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> (T, T) {
+        (point.x, point.y)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let mut p = Point::new(3, -2);
+        assert_eq!(p + Point::new(1, 1), Point::new(4, -1));
+        assert_eq!(p - Point::new(1, 1), Point::new(2, -3));
+        assert_eq!(p * 3, Point::new(9, -6));
+        assert_eq!(-p, Point::new(-3, 2));
+        p += Point::new(-3, 2);
+        assert_eq!(p, Point::ORIGIN);
+    }
+
+    #[test]
+    fn distances() {
+        let p = Point::new(8, 7);
+        assert_eq!(p.manhattan_distance(Point::new(2, 10)), 9);
+        assert_eq!(p.chebyshev_distance(Point::new(2, 10)), 6);
+        assert_eq!(p.manhattan_distance(p), 0);
+    }
+
+    #[test]
+    fn distances_of_unsigned_points() {
+        let p = Point::new(2usize, 10);
+        assert_eq!(p.manhattan_distance(Point::new(8, 7)), 9);
+        assert_eq!(Point::new(8, 7).manhattan_distance(p), 9);
+    }
+
+    #[test]
+    fn signum() {
+        assert_eq!(Point::new(-5, 0).signum(), Point::new(-1, 0));
+        assert_eq!(Point::new(2, -7).signum(), Point::new(1, -1));
+    }
+
+    #[test]
+    fn neighbors() {
+        let p = Point::new(0, 0);
+        assert_eq!(p.neighbors4().count(), 4);
+        let neighbors: Vec<_> = p.neighbors8().collect();
+        assert_eq!(neighbors.len(), 8);
+        assert!(neighbors.contains(&Point::new(-1, -1)));
+        assert!(neighbors.contains(&Point::new(1, 1)));
+        assert!(!neighbors.contains(&p));
+    }
+
+    #[test]
+    fn checked_add_signed() {
+        let p = Point::new(0usize, 3);
+        assert_eq!(
+            p.checked_add_signed(Point::new(1, -3)),
+            Some(Point::new(1, 0))
+        );
+        assert_eq!(p.checked_add_signed(Point::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn conversions() {
+        let p: Point<usize> = (1, 2).into();
+        assert_eq!(p.try_cast::<isize>(), Ok(Point::new(1, 2)));
+        assert!(Point::new(-1, 0).try_cast::<usize>().is_err());
+        assert_eq!(<(usize, usize)>::from(p), (1, 2));
+    }
+}
@@ -0,0 +1,379 @@
+use std::fmt::{Display, Formatter};
+use std::ops::{Index, IndexMut};
+
+use aoc_common::ParseError;
+
+/// A cell of a grid, addressed as `(row, column)`.
+pub type Pos = (usize, usize);
+
+/// Offsets to the four horizontally and vertically adjacent cells.
+pub const NEIGHBORS4: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// Offsets to all eight adjacent cells, including the diagonal ones.
+pub const NEIGHBORS8: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (1, 0),
+    (1, -1),
+    (0, -1),
+    (-1, -1),
+];
+
+/// A rectangular grid stored in row-major order.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its rows, which must all have the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "all rows of a grid must have the same length"
+        );
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Parses a character map with one row per line, converting every character with `cell`.
+    ///
+    /// Empty lines are skipped. `cell` receives the position the character ends up at and
+    /// returns `None` for characters it does not accept.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(Pos, char) -> Option<T>,
+    {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = Vec::new();
+        for (line_idx, line) in input.lines().enumerate() {
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut line_width = 0;
+            for (col, c) in line.chars().enumerate() {
+                let value = cell((height, col), c).ok_or_else(|| {
+                    ParseError::new(line_idx + 1, col + 1, format!("invalid character '{c}'"))
+                })?;
+                cells.push(value);
+                line_width += 1;
+            }
+
+            match width {
+                None => width = Some(line_width),
+                Some(width) if width != line_width => {
+                    return Err(ParseError::new(
+                        line_idx + 1,
+                        line_width.min(width) + 1,
+                        format!("expected {width} columns but found {line_width}"),
+                    ));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, (row, col): Pos) -> Option<&T> {
+        if row < self.height && col < self.width {
+            Some(&self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, (row, col): Pos) -> Option<&mut T> {
+        if row < self.height && col < self.width {
+            Some(&mut self.cells[row * self.width + col])
+        } else {
+            None
+        }
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> + '_ {
+        // `chunks` panics for a chunk size of 0, so empty rows are produced by hand
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, col: usize) -> impl DoubleEndedIterator<Item = &T> + '_ {
+        assert!(col < self.width, "column out of bounds");
+        (0..self.height).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    /// All positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| (row, col)))
+    }
+
+    /// All cells with their positions in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> + '_ {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The position `delta` away from `pos`, if it is inside the grid.
+    pub fn offset(&self, (row, col): Pos, (drow, dcol): (isize, isize)) -> Option<Pos> {
+        let row = row.checked_add_signed(drow)?;
+        let col = col.checked_add_signed(dcol)?;
+        (row < self.height && col < self.width).then_some((row, col))
+    }
+
+    /// The horizontally and vertically adjacent positions of `pos` inside the grid.
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS4
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    /// All adjacent positions of `pos` inside the grid, including diagonal ones.
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        NEIGHBORS8
+            .iter()
+            .filter_map(move |delta| self.offset(pos, *delta))
+    }
+
+    /// Inserts `cells` as row `row`, moving the rows from there on down. A grid without rows
+    /// takes its width from the new row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `row` is past the last row or the new row is not as wide as the grid.
+    pub fn insert_row(&mut self, row: usize, cells: Vec<T>) {
+        assert!(row <= self.height, "row out of bounds");
+        if self.height == 0 {
+            self.width = cells.len();
+        }
+        assert_eq!(
+            cells.len(),
+            self.width,
+            "the new row must be as wide as the grid"
+        );
+        let start = row * self.width;
+        self.cells.splice(start..start, cells);
+        self.height += 1;
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T> {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Grows the grid by the given number of rows and columns on each side.
+    pub fn expand(&mut self, top: usize, right: usize, bottom: usize, left: usize, fill: T) {
+        let width = left + self.width + right;
+        let height = top + self.height + bottom;
+        let mut cells = Vec::with_capacity(width * height);
+        cells.resize(top * width, fill.clone());
+        for row in self.rows() {
+            cells.extend(std::iter::repeat_n(fill.clone(), left));
+            cells.extend_from_slice(row);
+            cells.extend(std::iter::repeat_n(fill.clone(), right));
+        }
+        cells.resize(width * height, fill);
+
+        self.width = width;
+        self.height = height;
+        self.cells = cells;
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        self.get(pos).expect("position out of bounds")
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        self.get_mut(pos).expect("position out of bounds")
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn digits(input: &str) -> Result<Grid<u32>, ParseError> {
+        Grid::parse(input, |_, c| c.to_digit(10))
+    }
+
+    #[test]
+    fn parse_character_map() {
+        let grid = digits("123\n456\n").unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 2);
+        assert_eq!(grid[(0, 0)], 1);
+        assert_eq!(grid[(1, 2)], 6);
+        assert_eq!(grid.row(1), [4, 5, 6]);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), [2, 5]);
+    }
+
+    #[test]
+    fn parse_handles_crlf_and_blank_lines() {
+        let grid = digits("12\r\n\r\n34\r\n").unwrap();
+        assert_eq!(grid, Grid::from_rows(vec![vec![1, 2], vec![3, 4]]));
+    }
+
+    #[test]
+    fn parse_rejects_invalid_characters() {
+        let err = digits("123\n4x6\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 2, "invalid character 'x'"));
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        let err = digits("123\n45\n").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 3, "expected 3 columns but found 2"));
+
+        let err = digits("12\n\n345\n").err().unwrap();
+        assert_eq!(err, ParseError::new(3, 3, "expected 2 columns but found 3"));
+    }
+
+    #[test]
+    fn parse_passes_positions() {
+        let mut seen = Vec::new();
+        Grid::parse("ab\n\ncd\n", |pos, c| {
+            seen.push((pos, c));
+            Some(c)
+        })
+        .unwrap();
+        assert_eq!(
+            seen,
+            [((0, 0), 'a'), ((0, 1), 'b'), ((1, 0), 'c'), ((1, 1), 'd')]
+        );
+    }
+
+    #[test]
+    fn get_out_of_bounds() {
+        let grid = Grid::new(2, 3, 0);
+        assert_eq!(grid.get((2, 1)), Some(&0));
+        assert_eq!(grid.get((3, 1)), None);
+        assert_eq!(grid.get((2, 2)), None);
+    }
+
+    #[test]
+    fn neighbors_in_corner() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 0)]
+        );
+        assert_eq!(
+            grid.neighbors8((0, 0)).collect::<Vec<_>>(),
+            [(0, 1), (1, 1), (1, 0)]
+        );
+    }
+
+    #[test]
+    fn neighbors_in_center() {
+        let grid = Grid::new(3, 3, 0);
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn iterate_positions() {
+        let grid = Grid::from_rows(vec![vec!['a', 'b'], vec!['c', 'd']]);
+        let cells: Vec<_> = grid.iter().map(|(pos, c)| (pos, *c)).collect();
+        assert_eq!(
+            cells,
+            [((0, 0), 'a'), ((0, 1), 'b'), ((1, 0), 'c'), ((1, 1), 'd')]
+        );
+    }
+
+    #[test]
+    fn expand_on_all_sides() {
+        let mut grid = Grid::from_rows(vec![vec![1]]);
+        grid.expand(1, 2, 0, 1, 0);
+        assert_eq!(
+            grid,
+            Grid::from_rows(vec![vec![0, 0, 0, 0], vec![0, 1, 0, 0]])
+        );
+    }
+
+    #[test]
+    fn insert_rows() {
+        let mut grid = Grid::from_rows(vec![]);
+        grid.insert_row(0, vec![3, 4]);
+        grid.insert_row(0, vec![1, 2]);
+        grid.insert_row(2, vec![5, 6]);
+        assert_eq!(
+            grid,
+            Grid::from_rows(vec![vec![1, 2], vec![3, 4], vec![5, 6]])
+        );
+    }
+
+    #[test]
+    fn display() {
+        let grid = Grid::from_rows(vec![vec![1, 2], vec![3, 4]]);
+        assert_eq!(grid.to_string(), "12\n34\n");
+    }
+
+    #[test]
+    fn empty_grid() {
+        let grid = digits("").unwrap();
+        assert!(grid.is_empty());
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.positions().count(), 0);
+    }
+}